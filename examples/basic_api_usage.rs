@@ -1,6 +1,5 @@
 use paradex_rs::{Environment, Paradex};
 
-#[allow(clippy::await_holding_lock)]
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
@@ -9,32 +8,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let paradex = Paradex::new(Environment::Testnet)?;
 
     println!("Fetching system configuration...");
-    let config = paradex
-        .api_client()
-        .lock()
-        .unwrap()
-        .fetch_system_config()
-        .await?;
+    let config = paradex.api_client().fetch_system_config().await?;
     println!("System Config: {config:?}");
 
     println!("\nFetching system state...");
-    let state = paradex
-        .api_client()
-        .lock()
-        .unwrap()
-        .fetch_system_state()
-        .await?;
+    let state = paradex.api_client().fetch_system_state().await?;
     println!("System State: {state:?}");
 
     println!("\nFetching markets...");
-    let markets = paradex.api_client().lock().unwrap().fetch_markets().await?;
+    let markets = paradex.api_client().fetch_markets().await?;
     println!("Found {} markets", markets.results.len());
 
     println!("\nFetching market summary...");
     let summary = paradex
         .api_client()
-        .lock()
-        .unwrap()
         .fetch_markets_summary(Some("BTC-USD-PERP"))
         .await?;
     println!("Market Summary: {summary:?}");
@@ -12,7 +12,6 @@ use paradex_rs::{Environment, Paradex};
 // Note: WebSocketChannel import needed when uncommenting authenticated features
 // use paradex_rs::WebSocketChannel;
 
-#[allow(clippy::await_holding_lock)]
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
@@ -27,12 +26,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 2: Fetch system configuration
     println!("Step 2: Fetching system configuration...");
-    let config = paradex
-        .api_client()
-        .lock()
-        .unwrap()
-        .fetch_system_config()
-        .await?;
+    let config = paradex.api_client().fetch_system_config().await?;
     println!("✓ System config loaded");
     println!("  - L1 Chain ID: {}", config.l1_chain_id);
     println!("  - L2 Chain ID: {}", config.starknet_chain_id);
@@ -40,7 +34,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 3: Fetch markets
     println!("Step 3: Fetching available markets...");
-    let markets = paradex.api_client().lock().unwrap().fetch_markets().await?;
+    let markets = paradex.api_client().fetch_markets().await?;
     println!("✓ Found {} markets", markets.results.len());
     if let Some(first_market) = markets.results.first() {
         println!("  - Example market: {}", first_market.symbol);
@@ -51,8 +45,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Step 4: Fetching BTC-USD-PERP market summary...");
     let summary = paradex
         .api_client()
-        .lock()
-        .unwrap()
         .fetch_markets_summary(Some("BTC-USD-PERP"))
         .await?;
     if let Some(btc_summary) = summary.results.first() {
@@ -71,8 +63,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Step 5: Fetching orderbook...");
     let orderbook = paradex
         .api_client()
-        .lock()
-        .unwrap()
         .fetch_orderbook("BTC-USD-PERP", Some(5))
         .await?;
     println!("✓ Orderbook loaded");
@@ -96,7 +86,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 7: Fetch account summary
     println!("Step 7: Fetching account summary...");
-    let account_summary = paradex_auth.api_client().lock().unwrap()
+    let account_summary = paradex_auth.api_client()
         .fetch_account_summary().await?;
     println!("✓ Account Summary:");
     println!("  - Account: {}", account_summary.account);
@@ -106,7 +96,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 8: Fetch positions
     println!("Step 8: Fetching positions...");
-    let positions = paradex_auth.api_client().lock().unwrap()
+    let positions = paradex_auth.api_client()
         .fetch_positions().await?;
     println!("✓ Found {} positions", positions.results.len());
     for position in positions.results {
@@ -129,7 +119,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Sign the order
     let account = paradex_auth.account().unwrap();
-    account.lock().unwrap().sign_order(&mut order)?;
+    account.sign_order(&mut order).await?;
     println!("✓ Order created and signed");
     println!("  - Market: {}", order.market);
     println!("  - Side: {}", order.order_side);
@@ -139,7 +129,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 10: Submit order (COMMENTED OUT - REMOVE TO ACTUALLY TRADE)
     // println!("Step 10: Submitting order...");
-    // let order_result = paradex_auth.api_client().lock().unwrap()
+    // let order_result = paradex_auth.api_client()
     //     .submit_order(&order).await?;
     // println!("✓ Order submitted successfully");
     // println!("  - Order ID: {}", order_result.id);
@@ -148,13 +138,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Step 11: Connect WebSocket and subscribe to channels
     println!("Step 11: Connecting to WebSocket...");
     let ws_client = paradex_auth.ws_client();
-    ws_client.lock().unwrap().connect().await?;
+    ws_client.connect().await?;
     println!("✓ WebSocket connected\n");
 
     println!("Step 12: Subscribing to market data channels...");
 
     // Subscribe to BBO (Best Bid/Offer)
-    ws_client.lock().unwrap().subscribe(
+    ws_client.subscribe(
         WebSocketChannel::BBO,
         Some("BTC-USD-PERP"),
         |message| {
@@ -168,7 +158,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("✓ Subscribed to BBO channel");
 
     // Subscribe to Trades
-    ws_client.lock().unwrap().subscribe(
+    ws_client.subscribe(
         WebSocketChannel::Trades,
         Some("BTC-USD-PERP"),
         |message| {
@@ -182,7 +172,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("✓ Subscribed to Trades channel");
 
     // Subscribe to Orders (private channel)
-    ws_client.lock().unwrap().subscribe(
+    ws_client.subscribe(
         WebSocketChannel::Orders,
         Some("BTC-USD-PERP"),
         |message| {
@@ -198,7 +188,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\nListening for WebSocket messages for 30 seconds...");
     tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
 
-    ws_client.lock().unwrap().close().await?;
+    ws_client.close().await?;
     println!("✓ WebSocket closed");
     */
 
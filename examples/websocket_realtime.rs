@@ -4,7 +4,6 @@
 
 use paradex_rs::{Environment, Paradex, WebSocketChannel};
 
-#[allow(clippy::await_holding_lock)]
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
@@ -20,53 +19,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Connect to WebSocket
     println!("Connecting to WebSocket...");
-    ws.lock().unwrap().connect().await?;
+    ws.connect().await?;
     println!("✓ Connected\n");
 
     // Subscribe to BBO (Best Bid/Offer) for BTC
     println!("Subscribing to BBO channel for BTC-USD-PERP...");
-    ws.lock()
-        .unwrap()
-        .subscribe(WebSocketChannel::BBO, Some("BTC-USD-PERP"), |message| {
-            Box::pin(async move {
-                if let Some(data) = message.get("data") {
-                    if let (Some(bid), Some(ask)) = (data.get("bid"), data.get("ask")) {
-                        println!("[BBO] Bid: {bid} | Ask: {ask}");
-                    }
+    ws.subscribe(WebSocketChannel::BBO, Some("BTC-USD-PERP"), |message| {
+        Box::pin(async move {
+            if let Some(data) = message.get("data") {
+                if let (Some(bid), Some(ask)) = (data.get("bid"), data.get("ask")) {
+                    println!("[BBO] Bid: {bid} | Ask: {ask}");
                 }
-            })
+            }
         })
-        .await?;
+    })
+    .await?;
     println!("✓ Subscribed to BBO\n");
 
     // Subscribe to Trades
     println!("Subscribing to Trades channel for BTC-USD-PERP...");
-    ws.lock()
-        .unwrap()
-        .subscribe(WebSocketChannel::Trades, Some("BTC-USD-PERP"), |message| {
-            Box::pin(async move {
-                if let Some(data) = message.get("data") {
-                    println!("[Trade] {data:?}");
-                }
-            })
+    ws.subscribe(WebSocketChannel::Trades, Some("BTC-USD-PERP"), |message| {
+        Box::pin(async move {
+            if let Some(data) = message.get("data") {
+                println!("[Trade] {data:?}");
+            }
         })
-        .await?;
+    })
+    .await?;
     println!("✓ Subscribed to Trades\n");
 
     // Subscribe to Market Summary
     println!("Subscribing to Market Summary for ALL markets...");
-    ws.lock()
-        .unwrap()
-        .subscribe_by_name("markets_summary.ALL", |message| {
-            Box::pin(async move {
-                if let Some(data) = message.get("data") {
-                    if let Some(symbol) = data.get("symbol") {
-                        println!("[Market Summary] {symbol}: {data:?}");
-                    }
+    ws.subscribe_by_name("markets_summary.ALL", |message| {
+        Box::pin(async move {
+            if let Some(data) = message.get("data") {
+                if let Some(symbol) = data.get("symbol") {
+                    println!("[Market Summary] {symbol}: {data:?}");
                 }
-            })
+            }
         })
-        .await?;
+    })
+    .await?;
     println!("✓ Subscribed to Market Summary\n");
 
     // Subscribe to OrderBook snapshots
@@ -74,22 +67,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let orderbook_channel =
         WebSocketChannel::OrderBook.with_params(&["BTC-USD-PERP", "15", "100", "1"]);
 
-    ws.lock()
-        .unwrap()
-        .subscribe_by_name(&orderbook_channel, |message| {
-            Box::pin(async move {
-                if let Some(data) = message.get("data") {
-                    if let (Some(bids), Some(asks)) = (data.get("bids"), data.get("asks")) {
-                        println!(
-                            "[OrderBook] Bids: {} | Asks: {}",
-                            bids.as_array().map(|a| a.len()).unwrap_or(0),
-                            asks.as_array().map(|a| a.len()).unwrap_or(0)
-                        );
-                    }
+    ws.subscribe_by_name(&orderbook_channel, |message| {
+        Box::pin(async move {
+            if let Some(data) = message.get("data") {
+                if let (Some(bids), Some(asks)) = (data.get("bids"), data.get("asks")) {
+                    println!(
+                        "[OrderBook] Bids: {} | Asks: {}",
+                        bids.as_array().map(|a| a.len()).unwrap_or(0),
+                        asks.as_array().map(|a| a.len()).unwrap_or(0)
+                    );
                 }
-            })
+            }
         })
-        .await?;
+    })
+    .await?;
     println!("✓ Subscribed to OrderBook\n");
 
     println!("Listening for WebSocket messages...");
@@ -101,7 +92,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("\n---");
     println!("Closing WebSocket connection...");
-    ws.lock().unwrap().close().await?;
+    ws.close().await?;
     println!("✓ Disconnected");
 
     println!("\n=== Example completed ===");
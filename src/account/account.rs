@@ -1,13 +1,15 @@
 use crate::{
-    account::key_derivation::{
-        build_stark_key_message, compute_account_address, compute_public_key, derive_stark_key,
-    },
+    account::key_derivation::{build_stark_key_message, compute_account_address, derive_stark_key},
     error::{ParadexError, Result},
+    secret::{SecretKey, SecretString},
+    signer::{InMemorySigner, Signer},
     types::SystemConfig,
 };
 use starknet_types_core::felt::Felt;
+use std::sync::{Arc, RwLock};
 
 /// Paradex account with L1 and L2 key management
+#[derive(Clone)]
 pub struct ParadexAccount {
     /// Ethereum (L1) address
     pub l1_address: String,
@@ -18,14 +20,14 @@ pub struct ParadexAccount {
     /// Starknet public key
     pub l2_public_key: Felt,
 
-    /// Starknet private key (kept private)
-    l2_private_key: Felt,
+    /// Signer holding the Starknet key
+    signer: Arc<dyn Signer>,
 
     /// L2 chain ID
     chain_id: Felt,
 
     /// JWT token for authentication
-    pub jwt_token: Option<String>,
+    jwt_token: Arc<RwLock<Option<SecretString>>>,
 }
 
 impl ParadexAccount {
@@ -33,7 +35,7 @@ impl ParadexAccount {
     pub async fn from_l1_private_key(
         config: &SystemConfig,
         l1_address: impl Into<String>,
-        l1_private_key: impl Into<String>,
+        l1_private_key: impl Into<SecretString>,
     ) -> Result<Self> {
         let l1_address = l1_address.into();
         let l1_private_key = l1_private_key.into();
@@ -55,10 +57,19 @@ impl ParadexAccount {
     pub fn from_l2_private_key(
         config: &SystemConfig,
         l1_address: impl Into<String>,
-        l2_private_key: Felt,
+        l2_private_key: impl Into<SecretKey>,
     ) -> Result<Self> {
-        // Compute public key from private key
-        let l2_public_key = compute_public_key(l2_private_key)?;
+        let signer = InMemorySigner::new(l2_private_key);
+        Self::from_signer(config, l1_address, Arc::new(signer))
+    }
+
+    /// Create a new account whose L2 key is held by `signer`
+    pub fn from_signer(
+        config: &SystemConfig,
+        l1_address: impl Into<String>,
+        signer: Arc<dyn Signer>,
+    ) -> Result<Self> {
+        let l2_public_key = signer.public_key();
 
         // Parse system config hashes
         let account_class_hash = Felt::from_hex(&config.paraclear_account_hash)
@@ -83,9 +94,9 @@ impl ParadexAccount {
             l1_address: l1_address.into(),
             l2_address,
             l2_public_key,
-            l2_private_key,
+            signer,
             chain_id,
-            jwt_token: None,
+            jwt_token: Arc::default(),
         })
     }
 
@@ -99,10 +110,9 @@ impl ParadexAccount {
         format!("{:#x}", self.l2_public_key)
     }
 
-    /// Get L2 private key (for signing)
-    #[allow(dead_code)]
-    pub(crate) fn l2_private_key(&self) -> Felt {
-        self.l2_private_key
+    /// Get the signer holding the L2 key
+    pub fn signer(&self) -> Arc<dyn Signer> {
+        Arc::clone(&self.signer)
     }
 
     /// Get chain ID
@@ -110,22 +120,19 @@ impl ParadexAccount {
         self.chain_id
     }
 
-    /// Set JWT token
-    pub fn set_jwt_token(&mut self, token: impl Into<String>) {
-        self.jwt_token = Some(token.into());
+    /// Set JWT token, shared with clones of this account
+    pub fn set_jwt_token(&self, token: impl Into<SecretString>) {
+        *self.jwt_token.write().unwrap() = Some(token.into());
     }
 
     /// Get JWT token
-    pub fn get_jwt_token(&self) -> Option<&str> {
-        self.jwt_token.as_deref()
+    pub fn get_jwt_token(&self) -> Option<SecretString> {
+        self.jwt_token.read().unwrap().clone()
     }
 
-    /// Sign a message hash with the L2 private key
-    pub fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)> {
-        let signature = starknet_crypto::sign(&self.l2_private_key, &hash, &self.l2_public_key)
-            .map_err(|e| ParadexError::SigningError(format!("Signing failed: {e}")))?;
-
-        Ok((signature.r, signature.s))
+    /// Sign a message hash with the account signer
+    pub async fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)> {
+        self.signer.sign_hash(hash).await
     }
 
     /// Flatten signature to hex string format
@@ -140,7 +147,7 @@ impl std::fmt::Debug for ParadexAccount {
             .field("l1_address", &self.l1_address)
             .field("l2_address", &self.l2_address_hex())
             .field("l2_public_key", &self.l2_public_key_hex())
-            .field("has_jwt_token", &self.jwt_token.is_some())
+            .field("has_jwt_token", &self.jwt_token.read().unwrap().is_some())
             .finish()
     }
 }
@@ -148,6 +155,7 @@ impl std::fmt::Debug for ParadexAccount {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::message::vectors;
 
     fn mock_system_config() -> SystemConfig {
         SystemConfig {
@@ -161,6 +169,7 @@ mod tests {
                 "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890".to_string(),
             paraclear_decimals: 8,
             bridged_tokens: vec![],
+            extra: Default::default(),
         }
     }
 
@@ -185,6 +194,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_account_vector() {
+        let vectors = vectors::load();
+        let expected = &vectors.account;
+
+        let account = ParadexAccount::from_l2_private_key(
+            &expected.system_config(),
+            &expected.l1_address,
+            vectors::felt(&expected.l2_private_key),
+        )
+        .unwrap();
+
+        assert_eq!(account.chain_id(), vectors::felt(&expected.chain_id));
+        assert_eq!(account.l2_public_key_hex(), expected.l2_public_key);
+        assert_eq!(account.l2_address_hex(), expected.l2_address);
+    }
+
+    #[tokio::test]
+    async fn test_sign_hash_nonce_is_not_reused() {
+        let l2_key = Felt::from_hex("0x1234").unwrap();
+        let account = ParadexAccount::from_l2_private_key(
+            &mock_system_config(),
+            "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb",
+            l2_key,
+        )
+        .unwrap();
+
+        let (r1, s1) = account.sign_hash(Felt::ONE).await.unwrap();
+        let (r2, _) = account.sign_hash(Felt::TWO).await.unwrap();
+        // A shared r means a shared nonce, from which the key can be solved
+        assert_ne!(r1, r2);
+        // Deterministic for the same hash
+        assert_eq!(account.sign_hash(Felt::ONE).await.unwrap(), (r1, s1));
+
+        // The public key must not be used as the nonce
+        let leaked = starknet_crypto::sign(&l2_key, &Felt::ONE, &account.l2_public_key).unwrap();
+        assert_ne!((r1, s1), (leaked.r, leaked.s));
+    }
+
     #[test]
     fn test_flatten_signature() {
         let r = Felt::from_hex("0x123").unwrap();
@@ -193,4 +241,24 @@ mod tests {
         assert!(flattened.starts_with("["));
         assert!(flattened.ends_with("]"));
     }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let config = mock_system_config();
+        let account = ParadexAccount::from_l2_private_key(
+            &config,
+            "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb",
+            Felt::from_hex("0xdeadbeefcafe").unwrap(),
+        )
+        .unwrap();
+        account.set_jwt_token("eyJhbGciOiJIUzI1NiJ9.secret");
+
+        let debug = format!("{account:?}");
+        assert!(!debug.contains("deadbeefcafe"));
+        assert!(!debug.contains("eyJhbGciOiJIUzI1NiJ9"));
+        assert_eq!(
+            account.get_jwt_token().unwrap().expose_secret(),
+            "eyJhbGciOiJIUzI1NiJ9.secret"
+        );
+    }
 }
@@ -7,18 +7,14 @@ use crate::{
 
 impl ParadexAccount {
     /// Sign a block trade
-    pub fn sign_block_trade(&self, block_trade: &BlockTradeRequest) -> Result<String> {
+    pub async fn sign_block_trade(&self, block_trade: &BlockTradeRequest) -> Result<String> {
         let typed_data = build_block_trade_message(self.chain_id(), block_trade);
-        let message_hash = typed_data.message_hash()?;
-        let (r, s) = self.sign_hash(message_hash)?;
-        Ok(Self::flatten_signature(r, s))
+        self.sign_typed_data(&typed_data).await
     }
 
     /// Sign a block offer
-    pub fn sign_block_offer(&self, offer: &BlockOfferRequest) -> Result<String> {
+    pub async fn sign_block_offer(&self, offer: &BlockOfferRequest) -> Result<String> {
         let typed_data = build_block_offer_message(self.chain_id(), offer);
-        let message_hash = typed_data.message_hash()?;
-        let (r, s) = self.sign_hash(message_hash)?;
-        Ok(Self::flatten_signature(r, s))
+        self.sign_typed_data(&typed_data).await
     }
 }
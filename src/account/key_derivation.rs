@@ -1,41 +1,96 @@
-use crate::error::{ParadexError, Result};
-use ethers::signers::{LocalWallet, Signer};
+use crate::{
+    error::{ParadexError, Result},
+    secret::{SecretKey, SecretString},
+};
+use ethers::{
+    signers::{LocalWallet, Signer},
+    types::{transaction::eip712::TypedData as Eip712TypedData, U256},
+};
 use futures::TryFutureExt;
+use sha2::{Digest, Sha256};
 use starknet_types_core::felt::Felt;
-use tiny_keccak::{Hasher, Keccak};
+
+/// Order of the Stark curve generator
+const EC_ORDER: &str = "0x800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f";
 
 /// Derive Stark key from Ethereum private key
-/// This matches the Python SDK's stark key derivation logic
-pub async fn derive_stark_key(eth_private_key: &str, message: &str) -> Result<Felt> {
+///
+/// This matches the key the Paradex web app and Python SDK derive: the
+/// Ethereum key signs the EIP-712 stark key message and the `r` component of
+/// the signature is ground into a valid Stark private key.
+pub async fn derive_stark_key(
+    eth_private_key: &SecretString,
+    message: &Eip712TypedData,
+) -> Result<SecretKey> {
     // Parse the Ethereum private key
     let wallet: LocalWallet = eth_private_key
+        .expose_secret()
         .parse()
         .map_err(|e| ParadexError::EthereumError(format!("Invalid private key: {e}")))?;
 
-    // Sign the message with the Ethereum key
+    // Sign the typed message with the Ethereum key
     let signature = wallet
-        .sign_message(message.as_bytes())
+        .sign_typed_data(message)
         .map_err(|e| ParadexError::EthereumError(format!("Signing failed: {e}")))
         .await?;
 
-    // Convert signature to bytes (r + s, 64 bytes)
-    let sig_bytes = signature.to_vec();
+    let mut key_seed = [0u8; 32];
+    signature.r.to_big_endian(&mut key_seed);
 
-    // Hash the signature to get a 256-bit value for Stark key
-    let mut hasher = Keccak::v256();
-    hasher.update(&sig_bytes);
-    let mut output = [0u8; 32];
-    hasher.finalize(&mut output);
+    Ok(SecretKey::from_felt(grind_key(&key_seed)))
+}
 
-    // Convert to Felt, ensuring it's within the field
-    let stark_key = Felt::from_bytes_be(&output);
+/// Build the EIP-712 stark key derivation message for the L1 chain
+pub fn build_stark_key_message(chain_id: u64) -> Eip712TypedData {
+    serde_json::from_value(serde_json::json!({
+        "domain": { "name": "Paradex", "version": "1", "chainId": chain_id },
+        "primaryType": "Constant",
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" }
+            ],
+            "Constant": [{ "name": "action", "type": "string" }]
+        },
+        "message": { "action": "STARK Key" }
+    }))
+    .expect("stark key message is valid EIP-712 typed data")
+}
 
-    Ok(stark_key)
+/// Grind a 256-bit seed into a Stark private key below the curve order
+///
+/// Implements StarkWare's `grind_key`: hash `seed || index` with SHA-256,
+/// rejecting values that would bias the reduction modulo the curve order.
+pub fn grind_key(key_seed: &[u8; 32]) -> Felt {
+    let order = U256::from_str_radix(EC_ORDER, 16).expect("valid curve order");
+    // 2^256 - (2^256 mod order)
+    let remainder = (U256::MAX % order + 1) % order;
+    let max_allowed_value = U256::MAX - remainder + 1;
+
+    let mut index = 0u64;
+    loop {
+        let mut index_bytes = [0u8; 32];
+        U256::from(index).to_big_endian(&mut index_bytes);
+
+        let mut hasher = Sha256::new();
+        hasher.update(minimal_bytes(key_seed));
+        hasher.update(minimal_bytes(&index_bytes));
+        let key = U256::from_big_endian(&hasher.finalize());
+
+        if key < max_allowed_value {
+            let mut bytes = [0u8; 32];
+            (key % order).to_big_endian(&mut bytes);
+            return Felt::from_bytes_be(&bytes);
+        }
+        index += 1;
+    }
 }
 
-/// Build the stark key derivation message for signing
-pub fn build_stark_key_message(chain_id: u64) -> String {
-    format!("Paradex Stark Key Derivation: {chain_id}")
+/// Big-endian bytes without leading zeros, keeping at least one byte
+fn minimal_bytes(value: &[u8; 32]) -> &[u8] {
+    let start = value.iter().position(|byte| *byte != 0).unwrap_or(31);
+    &value[start..]
 }
 
 /// Compute Starknet public key from private key
@@ -79,11 +134,31 @@ pub fn compute_account_address(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde::Deserialize;
 
     #[test]
     fn test_build_stark_key_message() {
         let message = build_stark_key_message(1);
-        assert_eq!(message, "Paradex Stark Key Derivation: 1");
+        assert_eq!(message.primary_type, "Constant");
+        assert_eq!(message.domain.chain_id, Some(U256::from(1)));
+        assert_eq!(message.message["action"], "STARK Key");
+    }
+
+    #[test]
+    fn test_grind_key() {
+        // Reference vector from StarkWare's key derivation
+        let seed = U256::from_str_radix(
+            "0x86F3E7293141F20A8BAFF320E8EE4ACCB9D4A4BF2B4D295E8CEE784DB46E0519",
+            16,
+        )
+        .unwrap();
+        let mut seed_bytes = [0u8; 32];
+        seed.to_big_endian(&mut seed_bytes);
+        assert_eq!(
+            grind_key(&seed_bytes),
+            Felt::from_hex("0x5c8c8683596c732541a59e03007b2d30dbbbb873556fe65b5fb63c16688f941")
+                .unwrap()
+        );
     }
 
     #[test]
@@ -96,4 +171,56 @@ mod tests {
         let public_key = compute_public_key(private_key);
         assert!(public_key.is_ok());
     }
+
+    /// Fixed L1 keys and the L2 keys and addresses derived from them. Each
+    /// account records its `source`; tests/vectors/reference/key_derivation_py.py
+    /// regenerates them with paradex-py.
+    #[derive(Deserialize)]
+    struct KeyDerivationVectors {
+        paraclear_account_hash: String,
+        paraclear_account_proxy_hash: String,
+        accounts: Vec<KeyDerivationVector>,
+    }
+
+    #[derive(Deserialize)]
+    struct KeyDerivationVector {
+        l1_private_key: String,
+        l1_chain_id: String,
+        l1_address: String,
+        l2_private_key: String,
+        l2_public_key: String,
+        l2_address: String,
+    }
+
+    #[tokio::test]
+    async fn test_key_derivation_vectors() {
+        let vectors: KeyDerivationVectors =
+            serde_json::from_str(include_str!("../../tests/vectors/key_derivation.json")).unwrap();
+        let account_hash = Felt::from_hex(&vectors.paraclear_account_hash).unwrap();
+        let proxy_hash = Felt::from_hex(&vectors.paraclear_account_proxy_hash).unwrap();
+
+        for vector in &vectors.accounts {
+            let wallet: LocalWallet = vector.l1_private_key.parse().unwrap();
+            assert_eq!(
+                ethers::utils::to_checksum(&wallet.address(), None),
+                vector.l1_address
+            );
+
+            let chain_id = vector.l1_chain_id.parse().unwrap();
+            let private_key = derive_stark_key(
+                &SecretString::new(&vector.l1_private_key),
+                &build_stark_key_message(chain_id),
+            )
+            .await
+            .unwrap()
+            .expose_secret();
+            assert_eq!(format!("{private_key:#x}"), vector.l2_private_key);
+
+            let public_key = compute_public_key(private_key).unwrap();
+            assert_eq!(format!("{public_key:#x}"), vector.l2_public_key);
+
+            let address = compute_account_address(public_key, account_hash, proxy_hash).unwrap();
+            assert_eq!(format!("{address:#x}"), vector.l2_address);
+        }
+    }
 }
@@ -1,6 +1,7 @@
 //! Account management module
 //!
-//! Handles L1/L2 key derivation, account address computation, and message signing.
+//! Handles L1/L2 key derivation, account address computation, message signing
+//! and signature verification.
 
 #[allow(clippy::module_inception)]
 mod account;
@@ -8,8 +9,11 @@ mod block_trades_signing;
 mod key_derivation;
 mod l2_transfer;
 mod signing;
+mod verification;
 
 pub use account::ParadexAccount;
 pub use key_derivation::{
     build_stark_key_message, compute_account_address, compute_public_key, derive_stark_key,
+    grind_key,
 };
+pub use verification::{parse_signature, verify_block_offer, verify_order, verify_typed_data};
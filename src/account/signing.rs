@@ -3,15 +3,22 @@ use crate::{
     error::Result,
     message::{
         build_auth_message, build_modify_order_message, build_onboarding_message,
-        build_order_message,
+        build_order_message, TypedData,
     },
     types::Order,
 };
 use chrono::Utc;
 
 impl ParadexAccount {
+    /// Sign arbitrary SNIP-12 typed data (revision 0 or 1) with this account
+    pub async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<String> {
+        let message_hash = typed_data.message_hash(self.l2_address)?;
+        let (r, s) = self.sign_hash(message_hash).await?;
+        Ok(Self::flatten_signature(r, s))
+    }
+
     /// Sign an order for submission
-    pub fn sign_order(&self, order: &mut Order) -> Result<String> {
+    pub async fn sign_order(&self, order: &mut Order) -> Result<String> {
         // Set signature timestamp if not already set
         if order.signature_timestamp.is_none() {
             order.signature_timestamp = Some(Utc::now().timestamp_millis());
@@ -19,19 +26,13 @@ impl ParadexAccount {
 
         // Build the appropriate message based on whether it's a modification
         let typed_data = if order.id.is_some() {
-            build_modify_order_message(self.chain_id(), order)
+            build_modify_order_message(self.chain_id(), order)?
         } else {
-            build_order_message(self.chain_id(), order)
+            build_order_message(self.chain_id(), order)?
         };
 
-        // Compute message hash
-        let message_hash = typed_data.message_hash()?;
-
-        // Sign the hash
-        let (r, s) = self.sign_hash(message_hash)?;
-
-        // Flatten signature
-        let signature = Self::flatten_signature(r, s);
+        // Sign the message hash
+        let signature = self.sign_typed_data(&typed_data).await?;
 
         // Set signature on order
         order.signature = Some(signature.clone());
@@ -40,11 +41,9 @@ impl ParadexAccount {
     }
 
     /// Generate authentication headers for onboarding
-    pub fn onboarding_headers(&self) -> Result<Vec<(String, String)>> {
+    pub async fn onboarding_headers(&self) -> Result<Vec<(String, String)>> {
         let typed_data = build_onboarding_message(self.chain_id());
-        let message_hash = typed_data.message_hash()?;
-        let (r, s) = self.sign_hash(message_hash)?;
-        let signature = Self::flatten_signature(r, s);
+        let signature = self.sign_typed_data(&typed_data).await?;
 
         Ok(vec![
             (
@@ -60,14 +59,12 @@ impl ParadexAccount {
     }
 
     /// Generate authentication headers for JWT request
-    pub fn auth_headers(&self) -> Result<Vec<(String, String)>> {
+    pub async fn auth_headers(&self) -> Result<Vec<(String, String)>> {
         let timestamp = Utc::now().timestamp();
         let expiry = timestamp + 24 * 60 * 60; // 24 hours
 
         let typed_data = build_auth_message(self.chain_id(), timestamp, expiry);
-        let message_hash = typed_data.message_hash()?;
-        let (r, s) = self.sign_hash(message_hash)?;
-        let signature = Self::flatten_signature(r, s);
+        let signature = self.sign_typed_data(&typed_data).await?;
 
         Ok(vec![
             (
@@ -87,8 +84,11 @@ impl ParadexAccount {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::message::vectors;
+    use crate::signer::{FnSigner, InMemorySigner, Signer};
     use crate::types::{OrderSide, OrderType, SystemConfig};
     use starknet_types_core::felt::Felt;
+    use std::sync::Arc;
 
     fn mock_config() -> SystemConfig {
         SystemConfig {
@@ -102,11 +102,12 @@ mod tests {
                 "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890".to_string(),
             paraclear_decimals: 8,
             bridged_tokens: vec![],
+            extra: Default::default(),
         }
     }
 
-    #[test]
-    fn test_sign_order() {
+    #[tokio::test]
+    async fn test_sign_order() {
         let config = mock_config();
         let private_key =
             Felt::from_hex("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef")
@@ -137,7 +138,7 @@ mod tests {
             stp: None,
         };
 
-        let result = account.sign_order(&mut order);
+        let result = account.sign_order(&mut order).await;
         if let Err(e) = &result {
             eprintln!("Signing error: {e:?}");
         }
@@ -146,8 +147,8 @@ mod tests {
         assert!(order.signature_timestamp.is_some());
     }
 
-    #[test]
-    fn test_onboarding_headers() {
+    #[tokio::test]
+    async fn test_onboarding_headers() {
         let config = mock_config();
         let private_key =
             Felt::from_hex("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef")
@@ -160,9 +161,94 @@ mod tests {
         )
         .unwrap();
 
-        let headers = account.onboarding_headers();
+        let headers = account.onboarding_headers().await;
         assert!(headers.is_ok());
         let headers = headers.unwrap();
         assert_eq!(headers.len(), 3);
     }
+
+    fn vector_account(vectors: &vectors::SignatureVectors) -> ParadexAccount {
+        ParadexAccount::from_l2_private_key(
+            &vectors.account.system_config(),
+            &vectors.account.l1_address,
+            vectors::felt(&vectors.account.l2_private_key),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_signature_vectors() {
+        let vectors = vectors::load();
+        let account = vector_account(&vectors);
+
+        for vector in &vectors.messages {
+            let message_hash = vector
+                .typed_data(account.chain_id())
+                .message_hash(account.l2_address)
+                .unwrap();
+            let (r, s) = account.sign_hash(message_hash).await.unwrap();
+
+            assert_eq!(
+                r,
+                vectors::felt(&vector.r),
+                "r mismatch for {}",
+                vector.name
+            );
+            assert_eq!(
+                s,
+                vectors::felt(&vector.s),
+                "s mismatch for {}",
+                vector.name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_order_matches_vector() {
+        let vectors = vectors::load();
+        let account = vector_account(&vectors);
+        let vector = vectors
+            .messages
+            .iter()
+            .find(|vector| vector.kind == "order")
+            .unwrap();
+
+        let mut order: Order = serde_json::from_value(vector.input.clone()).unwrap();
+        let signature = account.sign_order(&mut order).await.unwrap();
+
+        assert_eq!(
+            signature,
+            ParadexAccount::flatten_signature(vectors::felt(&vector.r), vectors::felt(&vector.s))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_order_with_custom_signer() {
+        let vectors = vectors::load();
+        let key = InMemorySigner::new(vectors::felt(&vectors.account.l2_private_key));
+        let signer = FnSigner::new(key.public_key(), move |hash| {
+            let key = key.clone();
+            async move { key.sign_hash(hash).await }
+        });
+        let account = ParadexAccount::from_signer(
+            &vectors.account.system_config(),
+            &vectors.account.l1_address,
+            Arc::new(signer),
+        )
+        .unwrap();
+        assert_eq!(account.l2_address_hex(), vectors.account.l2_address);
+
+        let vector = vectors
+            .messages
+            .iter()
+            .find(|vector| vector.kind == "order")
+            .unwrap();
+        let mut order: Order = serde_json::from_value(vector.input.clone()).unwrap();
+        let signature = account.sign_order(&mut order).await.unwrap();
+
+        assert_eq!(
+            signature,
+            ParadexAccount::flatten_signature(vectors::felt(&vector.r), vectors::felt(&vector.s))
+        );
+    }
 }
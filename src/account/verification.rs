@@ -0,0 +1,257 @@
+use crate::{
+    error::SignatureError,
+    message::{
+        build_block_offer_message, build_modify_order_message, build_order_message, TypedData,
+    },
+    types::{BlockOfferRequest, Order},
+};
+use starknet_types_core::felt::Felt;
+
+/// Parse a `[r,s]` signature as produced by `ParadexAccount::flatten_signature`
+pub fn parse_signature(signature: &str) -> Result<(Felt, Felt), SignatureError> {
+    let malformed = |reason: &str| SignatureError::Malformed {
+        signature: signature.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let inner = signature
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| malformed("expected [r,s]"))?;
+
+    let parts: Vec<&str> = inner
+        .split(',')
+        .map(|part| part.trim().trim_matches('"'))
+        .collect();
+    let [r, s] = parts[..] else {
+        return Err(malformed("expected exactly two components"));
+    };
+
+    let parse = |component: &str| {
+        let felt = if component.starts_with("0x") {
+            Felt::from_hex(component)
+        } else {
+            Felt::from_dec_str(component)
+        };
+        felt.map_err(|_| malformed(&format!("invalid felt {component:?}")))
+    };
+
+    Ok((parse(r)?, parse(s)?))
+}
+
+/// Verify a signature over typed data signed by `account_address`
+///
+/// Returns the verified message hash.
+pub fn verify_typed_data(
+    typed_data: &TypedData,
+    account_address: Felt,
+    public_key: Felt,
+    signature: &str,
+) -> Result<Felt, SignatureError> {
+    let (r, s) = parse_signature(signature)?;
+    let message_hash = typed_data
+        .message_hash(account_address)
+        .map_err(|e| SignatureError::Message(e.to_string()))?;
+
+    // Out of range components or keys can never verify, report them as invalid
+    match starknet_crypto::verify(&public_key, &message_hash, &r, &s) {
+        Ok(true) => Ok(message_hash),
+        _ => Err(SignatureError::Invalid { message_hash }),
+    }
+}
+
+/// Verify the signature of a signed order
+pub fn verify_order(
+    chain_id: Felt,
+    order: &Order,
+    account_address: Felt,
+    public_key: Felt,
+) -> Result<Felt, SignatureError> {
+    let signature = order
+        .signature
+        .as_deref()
+        .ok_or_else(|| SignatureError::Malformed {
+            signature: String::new(),
+            reason: "order is not signed".to_string(),
+        })?;
+
+    let typed_data = if order.id.is_some() {
+        build_modify_order_message(chain_id, order)
+    } else {
+        build_order_message(chain_id, order)
+    }
+    .map_err(|e| SignatureError::Message(e.to_string()))?;
+
+    verify_typed_data(&typed_data, account_address, public_key, signature)
+}
+
+/// Verify the signature of a counterparty block offer
+pub fn verify_block_offer(
+    chain_id: Felt,
+    offer: &BlockOfferRequest,
+    account_address: Felt,
+    public_key: Felt,
+) -> Result<Felt, SignatureError> {
+    let typed_data = build_block_offer_message(chain_id, offer);
+    verify_typed_data(&typed_data, account_address, public_key, &offer.signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::ParadexAccount;
+    use crate::message::{build_auth_message, vectors};
+    use crate::types::{OrderSide, OrderType};
+
+    fn vector_account() -> ParadexAccount {
+        let vectors = vectors::load();
+        ParadexAccount::from_l2_private_key(
+            &vectors.account.system_config(),
+            &vectors.account.l1_address,
+            vectors::felt(&vectors.account.l2_private_key),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_signature_roundtrip() {
+        let r = Felt::from_hex("0x123").unwrap();
+        let s = Felt::from_hex("0x456").unwrap();
+        let signature = ParadexAccount::flatten_signature(r, s);
+        assert_eq!(parse_signature(&signature).unwrap(), (r, s));
+        assert_eq!(parse_signature(r#"["0x123", "1110"]"#).unwrap(), (r, s));
+    }
+
+    #[test]
+    fn test_parse_signature_malformed() {
+        for signature in ["", "0x1,0x2", "[0x1]", "[0x1,0x2,0x3]", "[0x1,zz]"] {
+            assert!(
+                matches!(
+                    parse_signature(signature),
+                    Err(SignatureError::Malformed { .. })
+                ),
+                "{signature:?} should be malformed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_signature_vectors() {
+        let vectors = vectors::load();
+        let chain_id = vectors::felt(&vectors.account.chain_id);
+        let address = vectors::felt(&vectors.account.l2_address);
+        let public_key = vectors::felt(&vectors.account.l2_public_key);
+
+        for vector in &vectors.messages {
+            let signature = ParadexAccount::flatten_signature(
+                vectors::felt(&vector.r),
+                vectors::felt(&vector.s),
+            );
+            let message_hash = verify_typed_data(
+                &vector.typed_data(chain_id),
+                address,
+                public_key,
+                &signature,
+            )
+            .unwrap_or_else(|e| panic!("{}: {e}", vector.name));
+            assert_eq!(message_hash, vectors::felt(&vector.message_hash));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_rejects_tampered_message() {
+        let account = vector_account();
+        let typed_data = build_auth_message(account.chain_id(), 1_700_000_000, 1_700_086_400);
+        let signature = account.sign_typed_data(&typed_data).await.unwrap();
+
+        let tampered = build_auth_message(account.chain_id(), 1_700_000_000, 1_800_000_000);
+        assert!(matches!(
+            verify_typed_data(
+                &tampered,
+                account.l2_address,
+                account.l2_public_key,
+                &signature
+            ),
+            Err(SignatureError::Invalid { .. })
+        ));
+
+        assert!(matches!(
+            verify_typed_data(&typed_data, account.l2_address, Felt::ONE, &signature),
+            Err(SignatureError::Invalid { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_signed_order() {
+        let account = vector_account();
+        let mut order = Order {
+            market: "ETH-USD-PERP".to_string(),
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Limit,
+            size: "0.25".to_string(),
+            price: Some("3100".to_string()),
+            signature_timestamp: Some(1_700_000_000_000),
+            client_id: None,
+            instruction: None,
+            reduce_only: None,
+            trigger_price: None,
+            signature: None,
+            id: None,
+            flags: None,
+            recv_window: None,
+            stp: None,
+        };
+
+        assert!(matches!(
+            verify_order(
+                account.chain_id(),
+                &order,
+                account.l2_address,
+                account.l2_public_key
+            ),
+            Err(SignatureError::Malformed { .. })
+        ));
+
+        account.sign_order(&mut order).await.unwrap();
+        assert!(verify_order(
+            account.chain_id(),
+            &order,
+            account.l2_address,
+            account.l2_public_key
+        )
+        .is_ok());
+
+        order.size = "0.26".to_string();
+        assert!(matches!(
+            verify_order(
+                account.chain_id(),
+                &order,
+                account.l2_address,
+                account.l2_public_key
+            ),
+            Err(SignatureError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_block_offer() {
+        let vectors = vectors::load();
+        let vector = vectors
+            .messages
+            .iter()
+            .find(|vector| vector.kind == "block_offer")
+            .unwrap();
+        let mut offer: BlockOfferRequest = serde_json::from_value(vector.input.clone()).unwrap();
+        offer.signature =
+            ParadexAccount::flatten_signature(vectors::felt(&vector.r), vectors::felt(&vector.s));
+
+        let chain_id = vectors::felt(&vectors.account.chain_id);
+        let address = vectors::felt(&vectors.account.l2_address);
+        let public_key = vectors::felt(&vectors.account.l2_public_key);
+        assert!(verify_block_offer(chain_id, &offer, address, public_key).is_ok());
+
+        offer.signature_timestamp += 1;
+        assert!(verify_block_offer(chain_id, &offer, address, public_key).is_err());
+    }
+}
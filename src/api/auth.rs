@@ -1,93 +1,111 @@
 use crate::{
-    error::{ParadexError, Result},
-    types::AuthResponse,
+    api::http_client::HttpClient,
+    error::Result,
+    secret::SecretString,
+    types::{AuthResponse, OnboardingOptions, OnboardingOutcome, OnboardingStatus},
 };
-use reqwest::Client;
-use serde_json::json;
-use std::time::{SystemTime, UNIX_EPOCH};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Check whether an account is already onboarded
+pub async fn onboarding_status(client: &HttpClient, public_key: &str) -> Result<bool> {
+    let request = client
+        .request(Method::GET, "onboarding")
+        .query(&[("public_key", public_key)]);
+
+    let status: OnboardingStatus = client
+        .execute_unauthenticated(Method::GET, "onboarding", request)
+        .await?;
+    Ok(status.exists)
+}
 
 /// Perform onboarding for a new account
+///
+/// The onboarding status is checked first, so calling this for an existing
+/// account is a no-op returning [`OnboardingOutcome::AlreadyOnboarded`].
 pub async fn onboard(
-    client: &Client,
-    api_url: &str,
+    client: &HttpClient,
     headers: Vec<(String, String)>,
     public_key: &str,
-) -> Result<()> {
-    let url = format!("{api_url}/onboarding");
-    let payload = json!({
-        "public_key": public_key
-    });
+    options: &OnboardingOptions,
+) -> Result<OnboardingOutcome> {
+    if onboarding_status(client, public_key).await? {
+        log::debug!("Account already onboarded");
+        return Ok(OnboardingOutcome::AlreadyOnboarded);
+    }
+
+    let payload = OnboardingRequest {
+        public_key,
+        options,
+    };
 
-    let mut request = client.post(&url).json(&payload);
+    let mut request = client.request(Method::POST, "onboarding").json(&payload);
 
     // Add custom headers
     for (key, value) in headers {
         request = request.header(key, value);
     }
 
-    let response = request.send().await?;
-
-    if response.status().is_success() {
-        Ok(())
-    } else {
-        let status = response.status();
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-
-        // Onboarding might fail if already onboarded - that's ok
-        if status.as_u16() == 400 && error_text.contains("already") {
-            log::debug!("Account already onboarded");
-            Ok(())
-        } else {
-            Err(ParadexError::ApiError {
-                status: status.as_u16(),
-                message: error_text,
-            })
-        }
-    }
+    client
+        .execute_unauthenticated::<serde_json::Value>(Method::POST, "onboarding", request)
+        .await?;
+    Ok(OnboardingOutcome::Created)
 }
 
 /// Authenticate and get JWT token
 pub async fn authenticate(
-    client: &Client,
-    api_url: &str,
+    client: &HttpClient,
     headers: Vec<(String, String)>,
     public_key: &str,
-) -> Result<String> {
-    let url = format!("{api_url}/auth/{public_key}");
+) -> Result<SecretString> {
+    let path = format!("auth/{public_key}");
 
-    let mut request = client.post(&url);
+    let mut request = client.request(Method::POST, &path);
 
     // Add custom headers
     for (key, value) in headers {
         request = request.header(key, value);
     }
 
-    let response = request.send().await?;
+    let auth_response: AuthResponse = client
+        .execute_unauthenticated(Method::POST, &path, request)
+        .await?;
+    log::debug!("Authenticated {public_key}: {auth_response:?}");
+    Ok(auth_response.jwt_token)
+}
 
-    if response.status().is_success() {
-        let auth_response: AuthResponse = response.json().await?;
-        Ok(auth_response.jwt_token)
-    } else {
-        let status = response.status();
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        Err(ParadexError::ApiError {
-            status: status.as_u16(),
-            message: error_text,
-        })
+/// Body of the onboarding request
+#[derive(Serialize)]
+struct OnboardingRequest<'a> {
+    public_key: &'a str,
+    #[serde(flatten)]
+    options: &'a OnboardingOptions,
+}
+
+/// Time before expiry at which a JWT is refreshed
+pub const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Expiry time read from the `exp` claim of a JWT
+///
+/// The signature is not verified, the token is only inspected to schedule a
+/// refresh.
+pub fn jwt_expiry(token: &str) -> Option<SystemTime> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: u64,
     }
+
+    let payload = token.split('.').nth(1)?;
+    let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: Claims = serde_json::from_slice(&payload).ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(claims.exp))
 }
 
-/// Check if JWT token needs refresh (older than 4 minutes)
-pub fn needs_refresh(auth_timestamp: SystemTime) -> bool {
-    let now = SystemTime::now();
-    let elapsed = now.duration_since(auth_timestamp).unwrap_or_default();
-    elapsed.as_secs() > 4 * 60 // 4 minutes
+/// Check if a JWT expiring at `expires_at` should be refreshed now
+pub fn needs_refresh(expires_at: SystemTime) -> bool {
+    SystemTime::now() + REFRESH_MARGIN >= expires_at
 }
 
 /// Get current timestamp
@@ -106,14 +124,29 @@ pub fn timestamp_secs() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Mutex, OnceLock};
 
     #[test]
     fn test_needs_refresh() {
-        let old_time = SystemTime::now() - std::time::Duration::from_secs(5 * 60);
-        assert!(needs_refresh(old_time));
+        assert!(needs_refresh(SystemTime::now() - Duration::from_secs(5)));
+        assert!(needs_refresh(SystemTime::now() + Duration::from_secs(30)));
+        assert!(!needs_refresh(
+            SystemTime::now() + Duration::from_secs(4 * 60)
+        ));
+    }
 
-        let recent_time = SystemTime::now() - std::time::Duration::from_secs(60);
-        assert!(!needs_refresh(recent_time));
+    #[test]
+    fn test_jwt_expiry() {
+        let payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"0x1","exp":1700000300}"#);
+        let token = format!("eyJhbGciOiJIUzI1NiJ9.{payload}.signature");
+        assert_eq!(
+            jwt_expiry(&token),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_300))
+        );
+
+        assert_eq!(jwt_expiry("not-a-jwt"), None);
+        let payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"0x1"}"#);
+        assert_eq!(jwt_expiry(&format!("header.{payload}.sig")), None);
     }
 
     #[test]
@@ -121,4 +154,180 @@ mod tests {
         let ts = timestamp_secs();
         assert!(ts > 0);
     }
+
+    #[tokio::test]
+    async fn test_onboard_new_account() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/onboarding?public_key=0x1")
+            .with_body(r#"{"address":"0x2","exists":false}"#)
+            .create_async()
+            .await;
+        let create = server
+            .mock("POST", "/onboarding")
+            .match_header("PARADEX-STARKNET-SIGNATURE", "[0x1,0x2]")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "public_key": "0x1",
+                "referral_code": "friend",
+            })))
+            .with_body("{}")
+            .create_async()
+            .await;
+
+        let options = OnboardingOptions {
+            referral_code: Some("friend".to_string()),
+            ..Default::default()
+        };
+        let headers = vec![(
+            "PARADEX-STARKNET-SIGNATURE".to_string(),
+            "[0x1,0x2]".to_string(),
+        )];
+        let outcome = onboard(
+            &HttpClient::with_api_url(server.url()).unwrap(),
+            headers,
+            "0x1",
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome, OnboardingOutcome::Created);
+        create.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_onboard_existing_account() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/onboarding?public_key=0x1")
+            .with_body(r#"{"address":"0x2","exists":true}"#)
+            .create_async()
+            .await;
+        let create = server
+            .mock("POST", "/onboarding")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let outcome = onboard(
+            &HttpClient::with_api_url(server.url()).unwrap(),
+            vec![],
+            "0x1",
+            &OnboardingOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome, OnboardingOutcome::AlreadyOnboarded);
+        create.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_onboard_failure_is_surfaced() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/onboarding?public_key=0x1")
+            .with_body(r#"{"exists":false}"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/onboarding")
+            .with_status(400)
+            .with_body(r#"{"error":"INVALID_REFERRAL_CODE","message":"already used"}"#)
+            .create_async()
+            .await;
+
+        let error = onboard(
+            &HttpClient::with_api_url(server.url()).unwrap(),
+            vec![],
+            "0x1",
+            &OnboardingOptions::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::ParadexError::ApiError { status: 400, .. }
+        ));
+        assert_eq!(
+            error.api_error_code().map(|code| code.as_str()),
+            Some("INVALID_REFERRAL_CODE")
+        );
+    }
+
+    /// Logger recording every line so tests can check what would be emitted
+    struct CaptureLogger(Mutex<Vec<String>>);
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            // Only this crate's lines, mockito logs raw requests on its own
+            metadata
+                .target()
+                .starts_with(module_path!().split("::").next().unwrap())
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn captured_logs() -> &'static CaptureLogger {
+        static LOGGER: OnceLock<&'static CaptureLogger> = OnceLock::new();
+        LOGGER.get_or_init(|| {
+            let logger: &'static CaptureLogger =
+                Box::leak(Box::new(CaptureLogger(Mutex::new(Vec::new()))));
+            log::set_logger(logger).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+            logger
+        })
+    }
+
+    #[tokio::test]
+    async fn test_secrets_not_logged() {
+        let logs = captured_logs();
+        let jwt = "eyJhbGciOiJIUzI1NiJ9.payload.signature";
+        let signature = "[0x5ec12e7,0x5ec12e8]";
+
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/onboarding?public_key=0x1")
+            .with_body(r#"{"exists":false}"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/onboarding")
+            .with_body("{}")
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/auth/0x1")
+            .with_body(format!(r#"{{"jwt_token":"{jwt}"}}"#))
+            .create_async()
+            .await;
+
+        let client = HttpClient::with_api_url(server.url()).unwrap();
+        let headers = vec![(
+            "PARADEX-STARKNET-SIGNATURE".to_string(),
+            signature.to_string(),
+        )];
+        let options = OnboardingOptions::default();
+        onboard(&client, headers.clone(), "0x1", &options)
+            .await
+            .unwrap();
+        let token = authenticate(&client, headers, "0x1").await.unwrap();
+        assert_eq!(token.expose_secret(), jwt);
+        assert!(!format!("{token:?}").contains(jwt));
+
+        let lines = logs.0.lock().unwrap().clone();
+        assert!(lines.iter().any(|line| line.contains("Authenticated 0x1")));
+        for line in lines.iter() {
+            assert!(!line.contains(jwt), "JWT leaked into log: {line}");
+            assert!(
+                !line.contains("5ec12e7"),
+                "signature leaked into log: {line}"
+            );
+        }
+    }
 }
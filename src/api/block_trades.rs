@@ -6,7 +6,6 @@ use crate::{
         BlockTradeRequest, PaginatedResponse,
     },
 };
-use serde_json::Value;
 
 /// Block trades API mixin
 pub struct BlockTradesApi<'a> {
@@ -56,7 +55,7 @@ impl<'a> BlockTradesApi<'a> {
     }
 
     /// Cancel a block trade
-    pub async fn cancel_block_trade(&self, block_trade_id: &str) -> Result<Value> {
+    pub async fn cancel_block_trade(&self, block_trade_id: &str) -> Result<BlockTradeDetail> {
         let path = format!("block-trades/{block_trade_id}");
         self.http_client.delete(&path).await
     }
@@ -105,7 +104,7 @@ impl<'a> BlockTradesApi<'a> {
         &self,
         block_trade_id: &str,
         offer_id: &str,
-    ) -> Result<Value> {
+    ) -> Result<BlockOfferDetail> {
         let path = format!("block-trades/{block_trade_id}/offers/{offer_id}");
         self.http_client.delete(&path).await
     }
@@ -117,9 +116,7 @@ impl<'a> BlockTradesApi<'a> {
         offer_id: &str,
         execution: &BlockExecuteRequest,
     ) -> Result<BlockOfferDetail> {
-        let path = format!(
-            "block-trades/{block_trade_id}/offers/{offer_id}/execute"
-        );
+        let path = format!("block-trades/{block_trade_id}/offers/{offer_id}/execute");
         self.http_client.post(&path, execution).await
     }
 }
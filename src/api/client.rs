@@ -1,4 +1,10 @@
-use crate::{api::http_client::HttpClient, environment::Environment, error::Result, types::*};
+use crate::{
+    api::{http_client::HttpClient, pagination::paginate},
+    environment::Environment,
+    error::Result,
+    types::*,
+};
+use futures::Stream;
 use std::collections::HashMap;
 
 /// API client for interacting with Paradex REST API
@@ -10,6 +16,7 @@ use std::collections::HashMap;
 /// - Positions (private)
 /// - Fills and trades
 /// - Funding and liquidations
+#[derive(Debug)]
 pub struct ApiClient {
     http_client: HttpClient,
 }
@@ -22,14 +29,54 @@ impl ApiClient {
         })
     }
 
+    /// Create an API client on top of a configured HTTP client
+    pub fn with_http_client(http_client: HttpClient) -> Self {
+        Self { http_client }
+    }
+
+    /// Base URL of the REST API
+    pub fn api_url(&self) -> &str {
+        self.http_client.api_url()
+    }
+
     /// Set JWT token for authenticated requests
-    pub fn set_token(&mut self, token: impl Into<String>) {
+    pub fn set_token(&self, token: impl Into<crate::secret::SecretString>) {
         self.http_client.set_token(token);
     }
 
-    /// Get the underlying HTTP client (for auth operations)
-    pub(crate) fn get_http_client(&self) -> reqwest::Client {
-        self.http_client.get_client()
+    /// Session holding the JWT, refreshed before expiry and after a 401
+    pub fn auth_session(&self) -> std::sync::Arc<crate::api::AuthSession> {
+        self.http_client.auth_session()
+    }
+
+    /// Share a session with other clients
+    pub(crate) fn set_auth_session(&mut self, session: std::sync::Arc<crate::api::AuthSession>) {
+        self.http_client.set_auth_session(session);
+    }
+
+    /// Replace the retry policy for transient failures
+    pub fn set_retry_policy(&self, retry_policy: crate::api::RetryPolicy) {
+        self.http_client.set_retry_policy(retry_policy);
+    }
+
+    /// Replace the client side rate limits
+    pub fn set_rate_limits(&self, config: crate::api::RateLimitConfig) {
+        self.http_client.set_rate_limits(config);
+    }
+
+    /// Requests that can be sent from `bucket` without waiting
+    pub fn rate_limit_remaining(&self, bucket: crate::api::RateLimitBucket) -> u32 {
+        self.http_client.rate_limiter().remaining(bucket)
+    }
+
+    /// Append a middleware to the chain every request runs through
+    pub fn add_middleware(&self, middleware: impl crate::api::Middleware + 'static) {
+        self.http_client.add_middleware(middleware);
+    }
+
+    /// HTTP client the endpoints and the auth calls go through
+    pub(crate) fn http_client(&self) -> &HttpClient {
+        &self.http_client
     }
 
     // PUBLIC ENDPOINTS
@@ -40,12 +87,12 @@ impl ApiClient {
     }
 
     /// Fetch system state
-    pub async fn fetch_system_state(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_system_state(&self) -> Result<SystemState> {
         self.http_client.get("system/state").await
     }
 
     /// Fetch system time
-    pub async fn fetch_system_time(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_system_time(&self) -> Result<ServerTime> {
         self.http_client.get("system/time").await
     }
 
@@ -90,29 +137,20 @@ impl ApiClient {
     }
 
     /// Fetch trades for a specific market
-    pub async fn fetch_trades(&self, market: &str) -> Result<PaginatedResponse<serde_json::Value>> {
-        self.http_client
-            .get_with_params("trades", &[("market", market)])
-            .await
+    pub async fn fetch_trades(&self, query: &TradesQuery) -> Result<PaginatedResponse<Trade>> {
+        self.http_client.get_with_query("trades", query).await
     }
 
     /// Fetch funding data
     pub async fn fetch_funding_data(
         &self,
-        market: Option<&str>,
-    ) -> Result<PaginatedResponse<serde_json::Value>> {
-        match market {
-            Some(m) => {
-                self.http_client
-                    .get_with_params("funding/data", &[("market", m)])
-                    .await
-            }
-            None => self.http_client.get("funding/data").await,
-        }
+        query: &FundingQuery,
+    ) -> Result<PaginatedResponse<FundingData>> {
+        self.http_client.get_with_query("funding/data", query).await
     }
 
     /// Fetch insurance fund information
-    pub async fn fetch_insurance_fund(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_insurance_fund(&self) -> Result<InsuranceFund> {
         self.http_client.get("insurance").await
     }
 
@@ -124,17 +162,17 @@ impl ApiClient {
     }
 
     /// Fetch account profile
-    pub async fn fetch_account_profile(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_account_profile(&self) -> Result<AccountProfile> {
         self.http_client.get("account/profile").await
     }
 
     /// Fetch account info
-    pub async fn fetch_account_info(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_account_info(&self) -> Result<AccountInfo> {
         self.http_client.get("account/info").await
     }
 
     /// Fetch sub-accounts
-    pub async fn fetch_subaccounts(&self) -> Result<PaginatedResponse<serde_json::Value>> {
+    pub async fn fetch_subaccounts(&self) -> Result<PaginatedResponse<AccountInfo>> {
         self.http_client.get("account/subaccounts").await
     }
 
@@ -164,8 +202,13 @@ impl ApiClient {
     }
 
     /// Fetch order history
-    pub async fn fetch_orders_history(&self) -> Result<PaginatedResponse<OrderResponse>> {
-        self.http_client.get("orders-history").await
+    pub async fn fetch_orders_history(
+        &self,
+        query: &OrdersHistoryQuery,
+    ) -> Result<PaginatedResponse<OrderResponse>> {
+        self.http_client
+            .get_with_query("orders-history", query)
+            .await
     }
 
     /// Fetch specific order by ID
@@ -197,13 +240,16 @@ impl ApiClient {
     }
 
     /// Cancel an order
-    pub async fn cancel_order(&self, order_id: &str) -> Result<serde_json::Value> {
+    ///
+    /// The exchange acknowledges with an empty response, the outcome arrives
+    /// on the order's status.
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
         let path = format!("orders/{order_id}");
         self.http_client.delete(&path).await
     }
 
     /// Cancel order by client ID
-    pub async fn cancel_order_by_client_id(&self, client_id: &str) -> Result<serde_json::Value> {
+    pub async fn cancel_order_by_client_id(&self, client_id: &str) -> Result<()> {
         let path = format!("orders/by_client_id/{client_id}");
         self.http_client.delete(&path).await
     }
@@ -239,50 +285,103 @@ impl ApiClient {
     }
 
     /// Fetch fills
-    pub async fn fetch_fills(&self, market: Option<&str>) -> Result<PaginatedResponse<Fill>> {
-        match market {
-            Some(m) => {
-                self.http_client
-                    .get_with_params("fills", &[("market", m)])
-                    .await
-            }
-            None => self.http_client.get("fills").await,
-        }
+    pub async fn fetch_fills(&self, query: &FillsQuery) -> Result<PaginatedResponse<Fill>> {
+        self.http_client.get_with_query("fills", query).await
     }
 
     /// Fetch tradebusts
-    pub async fn fetch_tradebusts(&self) -> Result<PaginatedResponse<serde_json::Value>> {
-        self.http_client.get("tradebusts").await
+    pub async fn fetch_tradebusts(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<PaginatedResponse<Tradebust>> {
+        self.http_client.get_with_query("tradebusts", query).await
     }
 
     /// Fetch funding payments
     pub async fn fetch_funding_payments(
         &self,
-        market: Option<&str>,
+        query: &FundingQuery,
     ) -> Result<PaginatedResponse<FundingPayment>> {
-        match market {
-            Some(m) => {
-                self.http_client
-                    .get_with_params("funding/payments", &[("market", m)])
-                    .await
-            }
-            None => self.http_client.get("funding/payments").await,
-        }
+        self.http_client
+            .get_with_query("funding/payments", query)
+            .await
     }
 
     /// Fetch transactions
-    pub async fn fetch_transactions(&self) -> Result<PaginatedResponse<Transaction>> {
-        self.http_client.get("transactions").await
+    pub async fn fetch_transactions(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<PaginatedResponse<Transaction>> {
+        self.http_client.get_with_query("transactions", query).await
     }
 
     /// Fetch transfers
-    pub async fn fetch_transfers(&self) -> Result<PaginatedResponse<Transfer>> {
-        self.http_client.get("transfers").await
+    pub async fn fetch_transfers(
+        &self,
+        query: &TransfersQuery,
+    ) -> Result<PaginatedResponse<Transfer>> {
+        self.http_client.get_with_query("transfers", query).await
     }
 
     /// Fetch liquidations
-    pub async fn fetch_liquidations(&self) -> Result<PaginatedResponse<serde_json::Value>> {
-        self.http_client.get("liquidations").await
+    pub async fn fetch_liquidations(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<PaginatedResponse<Liquidation>> {
+        self.http_client.get_with_query("liquidations", query).await
+    }
+
+    /// Stream all fills matching `query`, following pagination cursors
+    pub fn fills_stream(&self, query: FillsQuery) -> impl Stream<Item = Result<Fill>> + '_ {
+        paginate(&self.http_client, "fills", query)
+    }
+
+    /// Stream the full order history matching `query`
+    pub fn orders_history_stream(
+        &self,
+        query: OrdersHistoryQuery,
+    ) -> impl Stream<Item = Result<OrderResponse>> + '_ {
+        paginate(&self.http_client, "orders-history", query)
+    }
+
+    /// Stream all transactions matching `query`
+    pub fn transactions_stream(
+        &self,
+        query: HistoryQuery,
+    ) -> impl Stream<Item = Result<Transaction>> + '_ {
+        paginate(&self.http_client, "transactions", query)
+    }
+
+    /// Stream all transfers matching `query`
+    pub fn transfers_stream(
+        &self,
+        query: TransfersQuery,
+    ) -> impl Stream<Item = Result<Transfer>> + '_ {
+        paginate(&self.http_client, "transfers", query)
+    }
+
+    /// Stream all funding payments matching `query`
+    pub fn funding_payments_stream(
+        &self,
+        query: FundingQuery,
+    ) -> impl Stream<Item = Result<FundingPayment>> + '_ {
+        paginate(&self.http_client, "funding/payments", query)
+    }
+
+    /// Stream all liquidations matching `query`
+    pub fn liquidations_stream(
+        &self,
+        query: HistoryQuery,
+    ) -> impl Stream<Item = Result<Liquidation>> + '_ {
+        paginate(&self.http_client, "liquidations", query)
+    }
+
+    /// Stream all tradebusts matching `query`
+    pub fn tradebusts_stream(
+        &self,
+        query: HistoryQuery,
+    ) -> impl Stream<Item = Result<Tradebust>> + '_ {
+        paginate(&self.http_client, "tradebusts", query)
     }
 
     /// Fetch points data
@@ -299,7 +398,7 @@ impl ApiClient {
         start_at: i64,
         end_at: i64,
         price_kind: Option<&str>,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<Vec<Kline>> {
         let start_at_str = start_at.to_string();
         let end_at_str = end_at.to_string();
 
@@ -316,9 +415,11 @@ impl ApiClient {
             params.push(("price_kind", &price_kind_str));
         }
 
-        self.http_client
+        let klines: PaginatedResponse<Kline> = self
+            .http_client
             .get_with_params("markets/klines", &params)
-            .await
+            .await?;
+        Ok(klines.results)
     }
 
     // BLOCK TRADES API
@@ -349,7 +450,7 @@ impl ApiClient {
     }
 
     /// Cancel a block trade
-    pub async fn cancel_block_trade(&self, block_trade_id: &str) -> Result<serde_json::Value> {
+    pub async fn cancel_block_trade(&self, block_trade_id: &str) -> Result<BlockTradeDetail> {
         let block_trades = crate::api::block_trades::BlockTradesApi::new(&self.http_client);
         block_trades.cancel_block_trade(block_trade_id).await
     }
@@ -404,7 +505,7 @@ impl ApiClient {
         &self,
         block_trade_id: &str,
         offer_id: &str,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<BlockOfferDetail> {
         let block_trades = crate::api::block_trades::BlockTradesApi::new(&self.http_client);
         block_trades
             .cancel_block_trade_offer(block_trade_id, offer_id)
@@ -1,45 +1,104 @@
-use crate::{environment::Environment, error::Result};
-use reqwest::{Client, RequestBuilder, Response};
-use serde::de::DeserializeOwned;
-use std::time::Duration;
+use crate::{
+    api::{
+        middleware::{HttpResponse, Middleware, RequestInfo},
+        rate_limit::{RateLimitBucket, RateLimitConfig, RateLimiter},
+        retry::{is_retry_safe, is_transient_error, RetryPolicy},
+        session::AuthSession,
+    },
+    environment::Environment,
+    error::{ApiErrorCode, ParadexError, Result},
+    secret::SecretString,
+    types::query::query_params,
+};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 /// HTTP client for making requests to Paradex API
 pub struct HttpClient {
     client: Client,
     api_url: String,
-    jwt_token: Option<String>,
+    session: Arc<AuthSession>,
+    retry_policy: RwLock<RetryPolicy>,
+    rate_limiter: RwLock<Arc<RateLimiter>>,
+    middlewares: RwLock<Vec<Arc<dyn Middleware>>>,
 }
 
 impl HttpClient {
     /// Create a new HTTP client
     pub fn new(env: Environment) -> Result<Self> {
         let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
+        Ok(Self::with_client(client, env.api_url()))
+    }
 
-        Ok(Self {
+    /// Create a client sending requests through `client` to `api_url`
+    pub fn with_client(client: Client, api_url: impl Into<String>) -> Self {
+        Self {
             client,
-            api_url: env.api_url(),
-            jwt_token: None,
-        })
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            session: Arc::default(),
+            retry_policy: RwLock::default(),
+            rate_limiter: RwLock::default(),
+            middlewares: RwLock::default(),
+        }
+    }
+
+    /// Create a client against an arbitrary base URL, for tests
+    #[cfg(test)]
+    pub(crate) fn with_api_url(api_url: impl Into<String>) -> Result<Self> {
+        Ok(Self::with_client(Client::new(), api_url))
+    }
+
+    /// Base URL requests are sent to
+    pub fn api_url(&self) -> &str {
+        &self.api_url
     }
 
     /// Set JWT token for authenticated requests
-    pub fn set_token(&mut self, token: impl Into<String>) {
-        self.jwt_token = Some(token.into());
+    pub fn set_token(&self, token: impl Into<SecretString>) {
+        self.session.set_token(token);
+    }
+
+    /// Session holding the JWT sent with every request
+    pub fn auth_session(&self) -> Arc<AuthSession> {
+        Arc::clone(&self.session)
+    }
+
+    /// Share a session with other clients
+    pub fn set_auth_session(&mut self, session: Arc<AuthSession>) {
+        self.session = session;
+    }
+
+    /// Replace the retry policy for subsequent requests
+    pub fn set_retry_policy(&self, retry_policy: RetryPolicy) {
+        *self.retry_policy.write().unwrap() = retry_policy;
+    }
+
+    /// Replace the client side rate limits
+    pub fn set_rate_limits(&self, config: RateLimitConfig) {
+        *self.rate_limiter.write().unwrap() = Arc::new(RateLimiter::new(config));
+    }
+
+    /// Rate limiter applied to every request
+    pub fn rate_limiter(&self) -> Arc<RateLimiter> {
+        Arc::clone(&self.rate_limiter.read().unwrap())
+    }
+
+    /// Append a middleware to the chain every request runs through
+    pub fn add_middleware(&self, middleware: impl Middleware + 'static) {
+        self.middlewares.write().unwrap().push(Arc::new(middleware));
     }
 
-    /// Get the underlying reqwest client
-    pub(crate) fn get_client(&self) -> Client {
-        self.client.clone()
+    fn middlewares(&self) -> Vec<Arc<dyn Middleware>> {
+        self.middlewares.read().unwrap().clone()
     }
 
     /// Make a GET request
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
-        let url = format!("{}/{}", self.api_url, path);
-        let mut request = self.client.get(&url);
-        request = self.add_auth_header(request);
-
-        let response = request.send().await?;
-        self.handle_response(response).await
+        self.get_with_params(path, &[]).await
     }
 
     /// Make a GET request with query parameters
@@ -48,50 +107,51 @@ impl HttpClient {
         path: &str,
         params: &[(&str, &str)],
     ) -> Result<T> {
-        let url = format!("{}/{}", self.api_url, path);
-        let mut request = self.client.get(&url).query(params);
-        request = self.add_auth_header(request);
+        let request = self.request(Method::GET, path).query(params);
+        self.execute(Method::GET, path, request, true, true).await
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Make a GET request with a typed query serialized into the query string
+    pub async fn get_with_query<T: DeserializeOwned, Q: serde::Serialize>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<T> {
+        let params = query_params(query);
+        let params: Vec<(&str, &str)> = params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        self.get_with_params(path, &params).await
     }
 
     /// Make a POST request
+    ///
+    /// Retried only when the body carries a `client_id`.
     pub async fn post<T: DeserializeOwned, B: serde::Serialize>(
         &self,
         path: &str,
         body: &B,
     ) -> Result<T> {
-        let url = format!("{}/{}", self.api_url, path);
-        let mut request = self.client.post(&url).json(body);
-        request = self.add_auth_header(request);
-
-        let response = request.send().await?;
-        self.handle_response(response).await
+        self.send_json(Method::POST, path, body).await
     }
 
     /// Make a PUT request
+    ///
+    /// Retried only when the body carries a `client_id`.
     pub async fn put<T: DeserializeOwned, B: serde::Serialize>(
         &self,
         path: &str,
         body: &B,
     ) -> Result<T> {
-        let url = format!("{}/{}", self.api_url, path);
-        let mut request = self.client.put(&url).json(body);
-        request = self.add_auth_header(request);
-
-        let response = request.send().await?;
-        self.handle_response(response).await
+        self.send_json(Method::PUT, path, body).await
     }
 
     /// Make a DELETE request
     pub async fn delete<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
-        let url = format!("{}/{}", self.api_url, path);
-        let mut request = self.client.delete(&url);
-        request = self.add_auth_header(request);
-
-        let response = request.send().await?;
-        self.handle_response(response).await
+        let request = self.request(Method::DELETE, path);
+        self.execute(Method::DELETE, path, request, true, true)
+            .await
     }
 
     /// Make a DELETE request with body
@@ -100,36 +160,550 @@ impl HttpClient {
         path: &str,
         body: &B,
     ) -> Result<T> {
+        self.send_json(Method::DELETE, path, body).await
+    }
+
+    pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
         let url = format!("{}/{}", self.api_url, path);
-        let mut request = self.client.delete(&url).json(body);
-        request = self.add_auth_header(request);
+        self.client.request(method, url)
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    async fn send_json<T: DeserializeOwned, B: serde::Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let retry_safe = is_retry_safe(&method, Some(body));
+        let request = self.request(method.clone(), path).json(body);
+        self.execute(method, path, request, retry_safe, true).await
     }
 
-    fn add_auth_header(&self, request: RequestBuilder) -> RequestBuilder {
-        if let Some(token) = &self.jwt_token {
-            request.bearer_auth(token)
-        } else {
-            request
+    /// Send a request without the JWT, for the onboarding and auth calls
+    ///
+    /// The session is left alone, a refresh may be waiting on this very call.
+    pub(crate) async fn execute_unauthenticated<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        request: RequestBuilder,
+    ) -> Result<T> {
+        let retry_safe = is_retry_safe(&method, None::<&()>);
+        self.execute(method, path, request, retry_safe, false).await
+    }
+
+    /// Send a request, retrying transient failures when `retry_safe` allows it
+    ///
+    /// Every attempt, retries included, takes a token from the endpoint's
+    /// rate limit bucket. Private calls refresh the JWT shortly before it
+    /// expires, and a 401 triggers one re-authentication and resend, which is
+    /// safe for any method since the request was rejected unprocessed.
+    async fn execute<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        request: RequestBuilder,
+        retry_safe: bool,
+        authenticated: bool,
+    ) -> Result<T> {
+        let bucket = RateLimitBucket::for_request(&method, path);
+        if authenticated && bucket != RateLimitBucket::PublicRead {
+            self.session.refresh_if_needed().await?;
+        }
+
+        let retry_policy = self.retry_policy.read().unwrap().clone();
+        let rate_limiter = self.rate_limiter();
+        let mut attempt = 0;
+        let mut reauthenticated = false;
+        loop {
+            rate_limiter.acquire(bucket).await?;
+            let token = self.session.token().filter(|_| authenticated);
+            let Some(current) = request.try_clone() else {
+                let response = self.send(path, with_auth(request, token.as_ref())).await?;
+                return decode_response(&method, path, response);
+            };
+            let can_retry = retry_safe && attempt < retry_policy.max_retries;
+
+            let response = match self.send(path, with_auth(current, token.as_ref())).await {
+                Ok(response) => response,
+                Err(e) if can_retry && is_transient_error(&e) => {
+                    log::warn!("Request failed, retrying: {e}");
+                    tokio::time::sleep(retry_policy.backoff(attempt)).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            if response.status == StatusCode::UNAUTHORIZED
+                && authenticated
+                && !reauthenticated
+                && self.session.refresh_rejected(token.as_ref()).await?
+            {
+                reauthenticated = true;
+                continue;
+            }
+
+            match retry_policy
+                .retry_delay(&response, attempt)
+                .filter(|_| can_retry)
+            {
+                Some(delay) => {
+                    log::warn!("{} from {path}, retrying", response.status);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return decode_response(&method, path, response),
+            }
         }
     }
 
-    async fn handle_response<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
-        let status = response.status();
+    /// Run `request` through the middleware chain and read the response
+    async fn send(&self, path: &str, request: RequestBuilder) -> reqwest::Result<HttpResponse> {
+        let mut request = request.build()?;
+        let middlewares = self.middlewares();
+        for middleware in &middlewares {
+            middleware.on_request(&mut request);
+        }
 
-        if status.is_success() {
-            Ok(response.json().await?)
+        let info = RequestInfo::new(&request, path);
+        let started = Instant::now();
+        let result = match self.client.execute(request).await {
+            Ok(response) => HttpResponse::read(response).await,
+            Err(e) => Err(e),
+        };
+
+        let elapsed = started.elapsed();
+        for middleware in &middlewares {
+            match &result {
+                Ok(response) => middleware.on_response(&info, response, elapsed),
+                Err(error) => middleware.on_error(&info, error, elapsed),
+            }
+        }
+        result
+    }
+}
+
+fn with_auth(request: RequestBuilder, token: Option<&SecretString>) -> RequestBuilder {
+    match token {
+        Some(token) => request.bearer_auth(token.expose_secret()),
+        None => request,
+    }
+}
+
+/// Decode a successful response, or the Paradex error envelope of a failed one
+fn decode_response<T: DeserializeOwned>(
+    method: &Method,
+    path: &str,
+    response: HttpResponse,
+) -> Result<T> {
+    if response.status.is_success() {
+        // Some calls, such as order cancels, answer with an empty body
+        let body: &[u8] = if response.body.is_empty() {
+            b"null"
         } else {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            Err(crate::error::ParadexError::ApiError {
-                status: status.as_u16(),
-                message: error_text,
-            })
+            &response.body
+        };
+        Ok(serde_json::from_slice(body)?)
+    } else {
+        Err(api_error(method, path, &response))
+    }
+}
+
+/// Error envelope returned by the Paradex API
+#[derive(Deserialize)]
+struct ErrorEnvelope {
+    error: Option<ApiErrorCode>,
+    message: Option<String>,
+    data: Option<serde_json::Value>,
+}
+
+fn api_error(method: &Method, path: &str, response: &HttpResponse) -> ParadexError {
+    let status = response.status.as_u16();
+    let body = response.text().into_owned();
+
+    let (code, message, data) = match serde_json::from_str::<ErrorEnvelope>(&body) {
+        Ok(envelope) if envelope.error.is_some() || envelope.message.is_some() => (
+            envelope.error,
+            envelope.message.unwrap_or(body),
+            envelope.data.map(Box::new),
+        ),
+        _ => (None, body, None),
+    };
+
+    ParadexError::ApiError {
+        status,
+        code,
+        message,
+        data,
+        method: method.to_string(),
+        path: path.trim_start_matches('/').to_string(),
+    }
+}
+
+impl std::fmt::Debug for HttpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("api_url", &self.api_url)
+            .field("session", &self.session)
+            .field("retry_policy", &*self.retry_policy.read().unwrap())
+            .field("rate_limiter", &self.rate_limiter())
+            .field("middlewares", &self.middlewares.read().unwrap().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_token() {
+        let client = HttpClient::new(Environment::Testnet).unwrap();
+        client.set_token("eyJhbGciOiJIUzI1NiJ9.secret");
+
+        let debug = format!("{client:?}");
+        assert!(debug.contains("has_token: true"));
+        assert!(!debug.contains("eyJhbGciOiJIUzI1NiJ9"));
+    }
+
+    #[tokio::test]
+    async fn test_decodes_error_envelope() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/orders")
+            .with_status(400)
+            .with_body(
+                r#"{"error":"INSUFFICIENT_MARGIN","message":"not enough margin","data":{"required":"10"}}"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("DELETE", "/orders/42")
+            .with_status(404)
+            .with_body(r#"{"error":"SOMETHING_NEW","message":"new failure"}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/account")
+            .with_status(401)
+            .with_body("Unauthorized")
+            .create_async()
+            .await;
+
+        let client = HttpClient::with_api_url(server.url()).unwrap();
+        let body = serde_json::json!({"market": "BTC-USD-PERP"});
+        let error = client
+            .post::<serde_json::Value, _>("orders", &body)
+            .await
+            .unwrap_err();
+        match &error {
+            ParadexError::ApiError {
+                status,
+                code,
+                message,
+                data,
+                method,
+                path,
+            } => {
+                assert_eq!(*status, 400);
+                assert_eq!(code, &Some(ApiErrorCode::InsufficientMargin));
+                assert_eq!(message, "not enough margin");
+                assert_eq!(data.as_ref().unwrap()["required"], "10");
+                assert_eq!((method.as_str(), path.as_str()), ("POST", "orders"));
+            }
+            other => panic!("unexpected error: {other}"),
         }
+        assert_eq!(
+            error.to_string(),
+            "API error (status 400) on POST orders: INSUFFICIENT_MARGIN: not enough margin"
+        );
+        assert!(!error.is_retryable());
+        assert!(!error.is_auth_error());
+
+        let error = client
+            .delete::<serde_json::Value>("orders/42")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.api_error_code(),
+            Some(&ApiErrorCode::Unknown("SOMETHING_NEW".to_string()))
+        );
+
+        let error = client
+            .get::<serde_json::Value>("account")
+            .await
+            .unwrap_err();
+        assert_eq!(error.api_error_code(), None);
+        assert!(error.is_auth_error());
+        assert!(error.to_string().ends_with("Unauthorized"));
+    }
+
+    /// Expiry of every token issued in these tests unless stated otherwise
+    const FAR_EXPIRY: i64 = 4_102_444_800;
+
+    /// Refresher handing out `token-1`, `token-2`, ... that never expire
+    #[derive(Default)]
+    struct CountingRefresher(std::sync::atomic::AtomicU32);
+
+    #[async_trait::async_trait]
+    impl crate::api::TokenRefresher for CountingRefresher {
+        async fn refresh(&self) -> Result<SecretString> {
+            let n = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            Ok(jwt(&format!("token-{n}"), FAR_EXPIRY).into())
+        }
+    }
+
+    /// Unsigned JWT expiring at `exp` seconds since the epoch
+    fn jwt(subject: &str, exp: i64) -> String {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+        let claims = URL_SAFE_NO_PAD.encode(format!(r#"{{"sub":"{subject}","exp":{exp}}}"#));
+        format!("eyJhbGciOiJub25lIn0.{claims}.")
+    }
+
+    #[tokio::test]
+    async fn test_reauthenticates_once_on_401() {
+        let mut server = mockito::Server::new_async().await;
+        let stale = jwt("stale", FAR_EXPIRY);
+        let rejected = server
+            .mock("GET", "/account")
+            .match_header("authorization", format!("Bearer {stale}").as_str())
+            .with_status(401)
+            .with_body(r#"{"error":"INVALID_TOKEN","message":"expired"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let accepted = server
+            .mock("GET", "/account")
+            .match_header(
+                "authorization",
+                format!("Bearer {}", jwt("token-1", FAR_EXPIRY)).as_str(),
+            )
+            .with_body(r#"{"account":"0x1"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let refresher = Arc::new(CountingRefresher::default());
+        let client = HttpClient::with_api_url(server.url()).unwrap();
+        client.set_token(stale);
+        client.auth_session().set_refresher(refresher.clone());
+
+        let body: serde_json::Value = client.get("account").await.unwrap();
+        assert_eq!(body["account"], "0x1");
+        assert_eq!(refresher.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+        rejected.assert_async().await;
+        accepted.assert_async().await;
+
+        // A second 401 after re-authenticating is returned to the caller
+        server
+            .mock("POST", "/orders")
+            .with_status(401)
+            .expect(2)
+            .create_async()
+            .await;
+        let error = client
+            .post::<serde_json::Value, _>("orders", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(error.is_auth_error());
+        assert_eq!(refresher.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_before_expiry() {
+        let mut server = mockito::Server::new_async().await;
+        let fresh = server
+            .mock("GET", "/positions")
+            .match_header(
+                "authorization",
+                format!("Bearer {}", jwt("token-1", FAR_EXPIRY)).as_str(),
+            )
+            .with_body(r#"{"results":[]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let public = server
+            .mock("GET", "/markets")
+            .with_body(r#"{"results":[]}"#)
+            .create_async()
+            .await;
+
+        let refresher = Arc::new(CountingRefresher::default());
+        let client = HttpClient::with_api_url(server.url()).unwrap();
+        client.set_token(jwt("expiring", chrono::Utc::now().timestamp() + 30));
+        client.auth_session().set_refresher(refresher.clone());
+
+        // Public reads do not trigger a refresh
+        client.get::<serde_json::Value>("markets").await.unwrap();
+        assert_eq!(refresher.0.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        client.get::<serde_json::Value>("positions").await.unwrap();
+        client.get::<serde_json::Value>("positions").await.unwrap();
+        assert_eq!(refresher.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+        fresh.assert_async().await;
+        public.assert_async().await;
+    }
+
+    fn fast_client(url: String) -> HttpClient {
+        let client = HttpClient::with_api_url(url).unwrap();
+        client.set_retry_policy(RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        });
+        client
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_fail_fast() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/markets")
+            .with_body("{}")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = HttpClient::with_api_url(server.url()).unwrap();
+        client.set_rate_limits(RateLimitConfig {
+            public_read: crate::api::Quota::per_minute(1),
+            mode: crate::api::RateLimitMode::FailFast,
+            ..RateLimitConfig::default()
+        });
+
+        client.get::<serde_json::Value>("markets").await.unwrap();
+        let error = client
+            .get::<serde_json::Value>("markets")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ParadexError::RateLimited {
+                bucket: RateLimitBucket::PublicRead,
+                ..
+            }
+        ));
+        assert_eq!(
+            client
+                .rate_limiter()
+                .remaining(RateLimitBucket::PrivateRead),
+            120
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_retries_transient_status() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/markets")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("GET", "/markets")
+            .with_body(r#"{"results":[]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = fast_client(server.url());
+        let body: serde_json::Value = client.get("markets").await.unwrap();
+        assert_eq!(body["results"], serde_json::json!([]));
+        unavailable.assert_async().await;
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("DELETE", "/orders/1")
+            .with_status(502)
+            .expect(4)
+            .create_async()
+            .await;
+
+        let client = fast_client(server.url());
+        let error = client
+            .delete::<serde_json::Value>("orders/1")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ParadexError::ApiError { status: 502, .. }));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_respects_retry_after() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/bbo/BTC-USD-PERP")
+            .with_status(429)
+            .with_header("retry-after", "1")
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/bbo/BTC-USD-PERP")
+            .with_body("{}")
+            .create_async()
+            .await;
+
+        let client = fast_client(server.url());
+        let started = std::time::Instant::now();
+        client
+            .get::<serde_json::Value>("bbo/BTC-USD-PERP")
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_order_post_retries_only_with_client_id() {
+        let mut server = mockito::Server::new_async().await;
+        let anonymous = server
+            .mock("POST", "/orders")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"market": "BTC-USD-PERP"}),
+            ))
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = fast_client(server.url());
+        let body = serde_json::json!({"market": "BTC-USD-PERP"});
+        assert!(client
+            .post::<serde_json::Value, _>("orders", &body)
+            .await
+            .is_err());
+        anonymous.assert_async().await;
+
+        let unavailable = server
+            .mock("POST", "/orders")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"client_id": "bot-1"}),
+            ))
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let accepted = server
+            .mock("POST", "/orders")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"client_id": "bot-1"}),
+            ))
+            .with_body(r#"{"id":"1"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let body = serde_json::json!({"market": "BTC-USD-PERP", "client_id": "bot-1"});
+        let response: serde_json::Value = client.post("orders", &body).await.unwrap();
+        assert_eq!(response["id"], "1");
+        unavailable.assert_async().await;
+        accepted.assert_async().await;
     }
 }
@@ -0,0 +1,260 @@
+use reqwest::{header::HeaderMap, Method, Request, StatusCode, Url};
+use std::{borrow::Cow, sync::Arc, time::Duration};
+
+/// Request as it was sent, after every middleware ran
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    pub method: Method,
+    pub url: Url,
+    /// API path the request was made for, such as `orders` or `auth/0x1`
+    pub path: String,
+}
+
+impl RequestInfo {
+    pub(crate) fn new(request: &Request, path: &str) -> Self {
+        Self {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            path: path.trim_start_matches('/').to_string(),
+        }
+    }
+}
+
+/// Response with its body already read
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub(crate) async fn read(response: reqwest::Response) -> reqwest::Result<Self> {
+        Ok(Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+
+    /// Body as text, with invalid UTF-8 replaced
+    pub fn text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+}
+
+/// Hook into every request sent by [`HttpClient`](crate::api::HttpClient)
+///
+/// Middlewares run in the order they were added. Retries and resends after a
+/// re-authentication go through the chain again. Every method defaults to a
+/// no-op.
+pub trait Middleware: Send + Sync {
+    /// Inspect or rewrite a request before it is sent
+    fn on_request(&self, _request: &mut Request) {}
+
+    /// Observe a response, `elapsed` covering the send and the body read
+    fn on_response(&self, _request: &RequestInfo, _response: &HttpResponse, _elapsed: Duration) {}
+
+    /// Observe a request that failed before a response was read
+    fn on_error(&self, _request: &RequestInfo, _error: &reqwest::Error, _elapsed: Duration) {}
+}
+
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    fn on_request(&self, request: &mut Request) {
+        (**self).on_request(request);
+    }
+
+    fn on_response(&self, request: &RequestInfo, response: &HttpResponse, elapsed: Duration) {
+        (**self).on_response(request, response, elapsed);
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &reqwest::Error, elapsed: Duration) {
+        (**self).on_error(request, error, elapsed);
+    }
+}
+
+/// Logs every request with its status and latency
+///
+/// Headers and bodies are not logged, they carry tokens and signatures.
+#[derive(Debug, Clone)]
+pub struct LoggingMiddleware {
+    level: log::Level,
+}
+
+impl LoggingMiddleware {
+    pub fn new(level: log::Level) -> Self {
+        Self { level }
+    }
+}
+
+impl Default for LoggingMiddleware {
+    fn default() -> Self {
+        Self::new(log::Level::Debug)
+    }
+}
+
+impl Middleware for LoggingMiddleware {
+    fn on_request(&self, request: &mut Request) {
+        log::log!(self.level, "{} {}", request.method(), request.url());
+    }
+
+    fn on_response(&self, request: &RequestInfo, response: &HttpResponse, elapsed: Duration) {
+        log::log!(
+            self.level,
+            "{} {} -> {} in {elapsed:?}",
+            request.method,
+            request.url,
+            response.status
+        );
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &reqwest::Error, elapsed: Duration) {
+        log::warn!(
+            "{} {} failed after {elapsed:?}: {error}",
+            request.method,
+            request.url
+        );
+    }
+}
+
+type TimingCallback = dyn Fn(&RequestInfo, Option<StatusCode>, Duration) + Send + Sync;
+
+/// Reports the latency of every request, for example to a histogram
+///
+/// The status is `None` when no response was received.
+pub struct TimingMiddleware {
+    record: Box<TimingCallback>,
+}
+
+impl TimingMiddleware {
+    pub fn new(
+        record: impl Fn(&RequestInfo, Option<StatusCode>, Duration) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            record: Box::new(record),
+        }
+    }
+}
+
+impl Middleware for TimingMiddleware {
+    fn on_response(&self, request: &RequestInfo, response: &HttpResponse, elapsed: Duration) {
+        (self.record)(request, Some(response.status), elapsed);
+    }
+
+    fn on_error(&self, request: &RequestInfo, _error: &reqwest::Error, elapsed: Duration) {
+        (self.record)(request, None, elapsed);
+    }
+}
+
+impl std::fmt::Debug for TimingMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimingMiddleware").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{HttpClient, RetryPolicy};
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Mutex,
+    };
+
+    struct RequestId;
+
+    impl Middleware for RequestId {
+        fn on_request(&self, request: &mut Request) {
+            request
+                .headers_mut()
+                .insert("x-request-id", "req-1".parse().unwrap());
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(String, Option<u16>, String)>>);
+
+    impl Middleware for Recorder {
+        fn on_response(&self, request: &RequestInfo, response: &HttpResponse, _elapsed: Duration) {
+            self.0.lock().unwrap().push((
+                request.path.clone(),
+                Some(response.status.as_u16()),
+                response.text().into_owned(),
+            ));
+        }
+
+        fn on_error(&self, request: &RequestInfo, _error: &reqwest::Error, _elapsed: Duration) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((request.path.clone(), None, String::new()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_sees_every_attempt() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/markets")
+            .match_header("x-request-id", "req-1")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("GET", "/markets")
+            .match_header("x-request-id", "req-1")
+            .with_body(r#"{"results":[]}"#)
+            .create_async()
+            .await;
+
+        let client = HttpClient::with_api_url(server.url()).unwrap();
+        client.set_retry_policy(RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            jitter: false,
+            ..RetryPolicy::default()
+        });
+        let recorder = Arc::new(Recorder::default());
+        let timed = Arc::new(AtomicU32::new(0));
+        client.add_middleware(RequestId);
+        client.add_middleware(Arc::clone(&recorder));
+        client.add_middleware(TimingMiddleware::new({
+            let timed = Arc::clone(&timed);
+            move |_, _, _| {
+                timed.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+
+        let _: serde_json::Value = client.get("markets").await.unwrap();
+        unavailable.assert_async().await;
+        ok.assert_async().await;
+
+        let recorded = recorder.0.lock().unwrap().clone();
+        assert_eq!(
+            recorded,
+            vec![
+                ("markets".to_string(), Some(503), String::new()),
+                (
+                    "markets".to_string(),
+                    Some(200),
+                    r#"{"results":[]}"#.to_string()
+                ),
+            ]
+        );
+        assert_eq!(timed.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_transport_error_reported() {
+        let client = HttpClient::with_api_url("http://127.0.0.1:1").unwrap();
+        client.set_retry_policy(RetryPolicy::none());
+        let recorder = Arc::new(Recorder::default());
+        client.add_middleware(Arc::clone(&recorder));
+
+        assert!(client.get::<serde_json::Value>("markets").await.is_err());
+        assert_eq!(
+            recorder.0.lock().unwrap().clone(),
+            vec![("markets".to_string(), None, String::new())]
+        );
+    }
+}
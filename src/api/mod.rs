@@ -2,10 +2,20 @@ pub mod auth;
 pub mod block_trades;
 pub mod client;
 pub mod http_client;
+pub mod middleware;
+pub mod pagination;
+pub mod rate_limit;
+pub mod retry;
+pub mod session;
 pub mod ws_client;
 
-pub use auth::{authenticate, needs_refresh, onboard};
+pub use auth::{authenticate, needs_refresh, onboard, onboarding_status};
 pub use block_trades::BlockTradesApi;
 pub use client::ApiClient;
 pub use http_client::HttpClient;
-pub use ws_client::{WebSocketChannel, WebSocketClient, WebSocketClientImpl};
+pub use middleware::{HttpResponse, LoggingMiddleware, Middleware, RequestInfo, TimingMiddleware};
+pub use pagination::Timestamped;
+pub use rate_limit::{Quota, RateLimitBucket, RateLimitConfig, RateLimitMode, RateLimiter};
+pub use retry::RetryPolicy;
+pub use session::{AuthSession, Jwt, TokenRefresher};
+pub use ws_client::{WebSocketChannel, WebSocketClient, WebSocketClientImpl, WebSocketConfig};
@@ -0,0 +1,249 @@
+use crate::{
+    api::http_client::HttpClient,
+    error::{ParadexError, Result},
+    types::{
+        Fill, FundingPayment, Liquidation, OrderResponse, Paged, PaginatedResponse, Tradebust,
+        Transaction, Transfer,
+    },
+};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use std::collections::{HashSet, VecDeque};
+
+/// Record carrying a creation time, used to stop streams at a time bound
+pub trait Timestamped {
+    /// Creation time in milliseconds since the epoch
+    fn created_at(&self) -> Option<i64>;
+}
+
+macro_rules! impl_timestamped {
+    ($($ty:ty),*) => {
+        $(impl Timestamped for $ty {
+            fn created_at(&self) -> Option<i64> {
+                Some(self.created_at)
+            }
+        })*
+    };
+}
+
+impl_timestamped!(
+    Fill,
+    FundingPayment,
+    Liquidation,
+    OrderResponse,
+    Tradebust,
+    Transaction,
+    Transfer
+);
+
+impl Timestamped for serde_json::Value {
+    fn created_at(&self) -> Option<i64> {
+        self.get("created_at").and_then(serde_json::Value::as_i64)
+    }
+}
+
+struct PageState<Q, T> {
+    query: Q,
+    items: VecDeque<T>,
+    cursor: Option<String>,
+    /// Cursors already requested, to stop on a repeating or cycling `next`
+    seen: HashSet<String>,
+    done: bool,
+}
+
+/// Stream every record of a paginated endpoint, following `next` cursors
+///
+/// Pages are returned newest first, so the stream ends at the first record
+/// older than `start_at`. Errors are yielded once and end the stream, as does
+/// a `next` cursor that was already followed.
+pub(crate) fn paginate<'a, Q, T>(
+    http_client: &'a HttpClient,
+    path: &'a str,
+    query: Q,
+) -> impl Stream<Item = Result<T>> + 'a
+where
+    Q: Paged + 'a,
+    T: DeserializeOwned + Timestamped + 'a,
+{
+    let page = query.page();
+    let start_at = page.start_at.map(|t| t.timestamp_millis());
+    let end_at = page.end_at.map(|t| t.timestamp_millis());
+    let state = PageState::<Q, T> {
+        cursor: page.cursor.clone(),
+        query,
+        items: VecDeque::new(),
+        seen: HashSet::new(),
+        done: false,
+    };
+
+    futures::stream::unfold(state, move |mut state| async move {
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                match item.created_at() {
+                    Some(ts) if start_at.is_some_and(|start| ts < start) => return None,
+                    Some(ts) if end_at.is_some_and(|end| ts > end) => continue,
+                    _ => return Some((Ok(item), state)),
+                }
+            }
+            if state.done {
+                return None;
+            }
+
+            if let Some(cursor) = &state.cursor {
+                if !state.seen.insert(cursor.clone()) {
+                    state.done = true;
+                    let message = format!("{path} returned cursor {cursor:?} more than once");
+                    return Some((Err(ParadexError::GenericError(message)), state));
+                }
+            }
+            state.query.page_mut().cursor = state.cursor.clone();
+            match http_client
+                .get_with_query::<PaginatedResponse<T>, _>(path, &state.query)
+                .await
+            {
+                Ok(page) => {
+                    state.cursor = page.next.filter(|cursor| !cursor.is_empty());
+                    state.done = state.cursor.is_none() || page.results.is_empty();
+                    state.items = page.results.into();
+                }
+                Err(e) => {
+                    state.done = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FillsQuery, HistoryQuery, TransfersQuery};
+    use chrono::{TimeZone, Utc};
+    use futures::StreamExt;
+    use mockito::Matcher;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn test_follows_cursors() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("GET", "/fills")
+            .match_query(Matcher::Regex("^market=BTC-USD-PERP&page_size=2$".into()))
+            .with_body(
+                json!({
+                    "results": [{"id": "3", "created_at": 3000}, {"id": "2", "created_at": 2000}],
+                    "next": "page-2",
+                    "prev": null,
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/fills")
+            .match_query(Matcher::UrlEncoded("cursor".into(), "page-2".into()))
+            .with_body(
+                json!({"results": [{"id": "1", "created_at": 1000}], "next": null, "prev": "x"})
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let http_client = HttpClient::with_api_url(server.url()).unwrap();
+        let query = FillsQuery::new().market("BTC-USD-PERP").page_size(2);
+        let ids: Vec<String> = paginate::<_, Value>(&http_client, "fills", query)
+            .map(|item| item.unwrap()["id"].as_str().unwrap().to_string())
+            .collect()
+            .await;
+
+        assert_eq!(ids, ["3", "2", "1"]);
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_stops_at_time_bound() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/transfers")
+            .match_query(Matcher::UrlEncoded("start_at".into(), "2000".into()))
+            .with_body(
+                json!({
+                    "results": [
+                        {"id": "4", "created_at": 4000},
+                        {"id": "3", "created_at": 3000},
+                        {"id": "1", "created_at": 1000},
+                    ],
+                    "next": "more",
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let http_client = HttpClient::with_api_url(server.url()).unwrap();
+        let query = TransfersQuery::new()
+            .start_at(Utc.timestamp_millis_opt(2000).unwrap())
+            .end_at(Utc.timestamp_millis_opt(3500).unwrap());
+        let ids: Vec<String> = paginate::<_, Value>(&http_client, "transfers", query)
+            .map(|item| item.unwrap()["id"].as_str().unwrap().to_string())
+            .collect()
+            .await;
+
+        assert_eq!(ids, ["3"]);
+    }
+
+    #[tokio::test]
+    async fn test_error_ends_stream() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/liquidations")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let http_client = HttpClient::with_api_url(server.url()).unwrap();
+        let items: Vec<Result<Value>> = paginate(&http_client, "liquidations", HistoryQuery::new())
+            .collect()
+            .await;
+
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_cursor_ends_stream() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("GET", "/transfers")
+            .match_query(Matcher::Regex("^page_size=1$".into()))
+            .with_body(json!({"results": [{"id": "2"}], "next": "page-2"}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        // The server keeps pointing back at the page it just returned
+        let cycle = server
+            .mock("GET", "/transfers")
+            .match_query(Matcher::Regex("cursor=page-2".into()))
+            .with_body(json!({"results": [{"id": "1"}], "next": "page-2"}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let http_client = HttpClient::with_api_url(server.url()).unwrap();
+        let query = TransfersQuery::new().page_size(1);
+        let items: Vec<Result<Value>> = paginate(&http_client, "transfers", query).collect().await;
+
+        assert_eq!(items.len(), 3);
+        assert!(items[..2].iter().all(Result::is_ok));
+        assert!(items[2]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("page-2"));
+        first.assert_async().await;
+        cycle.assert_async().await;
+    }
+}
@@ -0,0 +1,308 @@
+use crate::error::{ParadexError, Result};
+use reqwest::Method;
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Group of endpoints sharing a rate limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitBucket {
+    /// Unauthenticated market data, limited per IP
+    PublicRead,
+    /// Account data reads, limited per account
+    PrivateRead,
+    /// Order submit, modify, cancel and batch calls
+    OrderAction,
+}
+
+impl RateLimitBucket {
+    /// Bucket charged for a request to `path`
+    pub fn for_request(method: &Method, path: &str) -> Self {
+        const PUBLIC: [&str; 8] = [
+            "system",
+            "markets",
+            "orderbook",
+            "bbo",
+            "trades",
+            "funding/data",
+            "insurance",
+            "points_data",
+        ];
+
+        let path = path.trim_start_matches('/');
+        let is_under = |prefix: &str| {
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        };
+
+        if *method != Method::GET && is_under("orders") {
+            RateLimitBucket::OrderAction
+        } else if *method == Method::GET && PUBLIC.iter().any(|prefix| is_under(prefix)) {
+            RateLimitBucket::PublicRead
+        } else {
+            RateLimitBucket::PrivateRead
+        }
+    }
+}
+
+impl fmt::Display for RateLimitBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitBucket::PublicRead => write!(f, "public reads"),
+            RateLimitBucket::PrivateRead => write!(f, "private reads"),
+            RateLimitBucket::OrderAction => write!(f, "order actions"),
+        }
+    }
+}
+
+/// Number of requests allowed per period, refilled continuously
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl Quota {
+    pub fn per_second(requests: u32) -> Self {
+        Self {
+            requests,
+            period: Duration::from_secs(1),
+        }
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            requests,
+            period: Duration::from_secs(60),
+        }
+    }
+}
+
+/// What to do when a bucket is empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitMode {
+    /// Wait until a token is available
+    #[default]
+    Queue,
+    /// Return [`ParadexError::RateLimited`] immediately
+    FailFast,
+}
+
+/// Quotas for each bucket
+///
+/// The defaults follow the limits published by Paradex: 1500 public requests
+/// per minute per IP, 120 private reads and 800 order actions per second per
+/// account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    pub public_read: Quota,
+    pub private_read: Quota,
+    pub order_action: Quota,
+    pub mode: RateLimitMode,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            public_read: Quota::per_minute(1500),
+            private_read: Quota::per_second(120),
+            order_action: Quota::per_second(800),
+            mode: RateLimitMode::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(quota: Quota) -> Self {
+        // An empty quota would never refill
+        let capacity = f64::from(quota.requests.max(1));
+        Self {
+            capacity,
+            refill_per_sec: capacity / quota.period.as_secs_f64(),
+            tokens: capacity,
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Take a token, or return how long until one is available
+    fn try_take(&mut self) -> std::result::Result<(), Duration> {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+}
+
+/// Client side token-bucket limiter with one bucket per [`RateLimitBucket`]
+#[derive(Debug)]
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    public_read: Mutex<TokenBucket>,
+    private_read: Mutex<TokenBucket>,
+    order_action: Mutex<TokenBucket>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            mode: config.mode,
+            public_read: Mutex::new(TokenBucket::new(config.public_read)),
+            private_read: Mutex::new(TokenBucket::new(config.private_read)),
+            order_action: Mutex::new(TokenBucket::new(config.order_action)),
+        }
+    }
+
+    fn bucket(&self, bucket: RateLimitBucket) -> &Mutex<TokenBucket> {
+        match bucket {
+            RateLimitBucket::PublicRead => &self.public_read,
+            RateLimitBucket::PrivateRead => &self.private_read,
+            RateLimitBucket::OrderAction => &self.order_action,
+        }
+    }
+
+    /// Take a token from `bucket`, waiting or failing according to the mode
+    pub async fn acquire(&self, bucket: RateLimitBucket) -> Result<()> {
+        loop {
+            let wait = match self.bucket(bucket).lock().unwrap().try_take() {
+                Ok(()) => return Ok(()),
+                Err(wait) => wait,
+            };
+
+            if self.mode == RateLimitMode::FailFast {
+                return Err(ParadexError::RateLimited {
+                    bucket,
+                    retry_after: wait,
+                });
+            }
+            log::debug!("Rate limit reached for {bucket}, waiting {wait:?}");
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Requests that can be sent from `bucket` right now
+    pub fn remaining(&self, bucket: RateLimitBucket) -> u32 {
+        let mut bucket = self.bucket(bucket).lock().unwrap();
+        bucket.refill();
+        bucket.tokens as u32
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimitConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_classification() {
+        use RateLimitBucket::*;
+
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::GET, "markets/summary"),
+            PublicRead
+        );
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::GET, "bbo/BTC-USD-PERP"),
+            PublicRead
+        );
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::GET, "funding/payments"),
+            PrivateRead
+        );
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::GET, "orders"),
+            PrivateRead
+        );
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::GET, "orders-history"),
+            PrivateRead
+        );
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::POST, "orders/batch"),
+            OrderAction
+        );
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::DELETE, "orders/123"),
+            OrderAction
+        );
+        assert_eq!(
+            RateLimitBucket::for_request(&Method::POST, "block-trades"),
+            PrivateRead
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_when_empty() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            order_action: Quota::per_minute(2),
+            mode: RateLimitMode::FailFast,
+            ..RateLimitConfig::default()
+        });
+
+        assert_eq!(limiter.remaining(RateLimitBucket::OrderAction), 2);
+        limiter.acquire(RateLimitBucket::OrderAction).await.unwrap();
+        limiter.acquire(RateLimitBucket::OrderAction).await.unwrap();
+        assert_eq!(limiter.remaining(RateLimitBucket::OrderAction), 0);
+
+        let error = limiter
+            .acquire(RateLimitBucket::OrderAction)
+            .await
+            .unwrap_err();
+        match error {
+            ParadexError::RateLimited {
+                bucket,
+                retry_after,
+            } => {
+                assert_eq!(bucket, RateLimitBucket::OrderAction);
+                assert!(retry_after > Duration::from_secs(25));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        // Other buckets are unaffected
+        assert_eq!(limiter.remaining(RateLimitBucket::PublicRead), 1500);
+    }
+
+    #[tokio::test]
+    async fn test_queue_waits_for_refill() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            private_read: Quota {
+                requests: 1,
+                period: Duration::from_millis(50),
+            },
+            ..RateLimitConfig::default()
+        });
+
+        let started = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire(RateLimitBucket::PrivateRead).await.unwrap();
+        }
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::api::middleware::HttpResponse;
+use rand::Rng;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Method, StatusCode,
+};
+use serde::Serialize;
+use std::time::Duration;
+
+/// Retry policy for transient HTTP failures
+///
+/// Rate limits (429), gateway errors (502, 503, 504) and connection failures
+/// are retried with exponential backoff. A `Retry-After` header from the
+/// server takes precedence over the computed delay, capped at `max_backoff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt, 0 disables retrying
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed delay and for `Retry-After`
+    pub max_backoff: Duration,
+    /// Growth factor between consecutive delays
+    pub multiplier: f64,
+    /// Randomize each delay between half and the full value
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `attempt`, starting at 0
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+        // Capped in f64 first, an overflowing `Duration` would panic
+        let seconds = (self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_backoff.as_secs_f64());
+        let delay = Duration::from_secs_f64(seconds);
+
+        if self.jitter {
+            delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+        } else {
+            delay
+        }
+    }
+
+    /// Delay before the next attempt after `response`, if it is worth retrying
+    pub(crate) fn retry_delay(&self, response: &HttpResponse, attempt: u32) -> Option<Duration> {
+        if !is_transient_status(response.status) {
+            return None;
+        }
+        let delay = match retry_after(&response.headers) {
+            // A misbehaving or hostile header must not stall the caller for hours
+            Some(delay) => delay.min(self.max_backoff),
+            None => self.backoff(attempt),
+        };
+        Some(delay)
+    }
+}
+
+/// Whether a status code signals a temporary condition
+pub fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Whether a transport error happened before a response could be read
+pub(crate) fn is_transient_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request()
+}
+
+/// Whether sending `body` again with `method` cannot create a duplicate
+///
+/// Reads and cancels are safe to repeat. Other writes are only repeated when
+/// they carry a `client_id`, which the exchange uses to reject duplicates.
+/// Batch bodies qualify when every order has one.
+pub(crate) fn is_retry_safe<B: Serialize + ?Sized>(method: &Method, body: Option<&B>) -> bool {
+    if *method == Method::GET || *method == Method::DELETE {
+        return true;
+    }
+
+    let has_client_id = |value: &serde_json::Value| {
+        value
+            .get("client_id")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|id| !id.is_empty())
+    };
+
+    match body.and_then(|body| serde_json::to_value(body).ok()) {
+        Some(serde_json::Value::Array(items)) => {
+            !items.is_empty() && items.iter().all(has_client_id)
+        }
+        Some(value) => has_client_id(&value),
+        None => false,
+    }
+}
+
+/// Parse `Retry-After` as delta seconds or an HTTP date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(250));
+        assert_eq!(policy.backoff(2), Duration::from_secs(1));
+        assert_eq!(policy.backoff(10), Duration::from_secs(10));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(10));
+
+        let jittered = RetryPolicy::default().backoff(1);
+        assert!(jittered >= Duration::from_millis(250) && jittered <= Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_retry_after_is_capped() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+        let response = |retry_after: &str| HttpResponse {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            headers: HeaderMap::from_iter([(RETRY_AFTER, retry_after.parse().unwrap())]),
+            body: Vec::new(),
+        };
+
+        assert_eq!(
+            policy.retry_delay(&response("3"), 0),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            policy.retry_delay(&response("86400"), 0),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            policy.retry_delay(&response("Fri, 31 Dec 2049 23:59:59 GMT"), 0),
+            Some(Duration::from_secs(10))
+        );
+        // Unparseable values fall back to the computed backoff
+        assert_eq!(
+            policy.retry_delay(&response("soon"), 0),
+            Some(Duration::from_millis(250))
+        );
+    }
+
+    #[test]
+    fn test_retry_safety() {
+        let body = json!({"market": "BTC-USD-PERP"});
+        assert!(is_retry_safe(&Method::GET, None::<&()>));
+        assert!(is_retry_safe(&Method::DELETE, Some(&body)));
+        assert!(!is_retry_safe(&Method::POST, Some(&body)));
+        assert!(!is_retry_safe(
+            &Method::POST,
+            Some(&json!({"client_id": ""}))
+        ));
+        assert!(is_retry_safe(
+            &Method::POST,
+            Some(&json!({"client_id": "a"}))
+        ));
+        assert!(is_retry_safe(
+            &Method::POST,
+            Some(&json!([{"client_id": "a"}, {"client_id": "b"}]))
+        ));
+        assert!(!is_retry_safe(
+            &Method::POST,
+            Some(&json!([{"client_id": "a"}, {"market": "ETH-USD-PERP"}]))
+        ));
+    }
+}
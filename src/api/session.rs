@@ -0,0 +1,256 @@
+use crate::{
+    api::auth::{jwt_expiry, REFRESH_MARGIN},
+    error::{ParadexError, Result},
+    secret::SecretString,
+};
+use async_trait::async_trait;
+use std::{
+    sync::{Arc, RwLock, Weak},
+    time::{Duration, SystemTime},
+};
+
+/// Lifetime assumed for tokens whose `exp` claim cannot be read
+const DEFAULT_LIFETIME: Duration = Duration::from_secs(5 * 60);
+
+/// Shortest pause between two background refresh checks
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delay before the background task tries again after a failed refresh
+const REFRESH_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Source of fresh JWTs, usually by signing a new auth request
+#[async_trait]
+pub trait TokenRefresher: Send + Sync {
+    async fn refresh(&self) -> Result<SecretString>;
+}
+
+/// JWT with the expiry read from its `exp` claim
+#[derive(Debug, Clone)]
+pub struct Jwt {
+    token: SecretString,
+    expires_at: SystemTime,
+}
+
+impl Jwt {
+    pub fn new(token: impl Into<SecretString>) -> Self {
+        let token = token.into();
+        let expires_at = jwt_expiry(token.expose_secret())
+            .unwrap_or_else(|| SystemTime::now() + DEFAULT_LIFETIME);
+        Self { token, expires_at }
+    }
+
+    pub fn token(&self) -> &SecretString {
+        &self.token
+    }
+
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+}
+
+/// JWT shared by the REST client, the WebSocket client and the refresh task
+///
+/// Once a [`TokenRefresher`] is installed, private calls refresh the token
+/// before it expires and re-authenticate once when a request gets a 401.
+pub struct AuthSession {
+    jwt: RwLock<Option<Jwt>>,
+    refresher: RwLock<Option<Arc<dyn TokenRefresher>>>,
+    refreshing: tokio::sync::Mutex<()>,
+    refresh_margin: Duration,
+}
+
+impl Default for AuthSession {
+    fn default() -> Self {
+        Self::with_refresh_margin(REFRESH_MARGIN)
+    }
+}
+
+impl AuthSession {
+    /// Session refreshing tokens `refresh_margin` before they expire
+    pub fn with_refresh_margin(refresh_margin: Duration) -> Self {
+        Self {
+            jwt: RwLock::default(),
+            refresher: RwLock::default(),
+            refreshing: tokio::sync::Mutex::default(),
+            refresh_margin,
+        }
+    }
+
+    /// Current token, if any
+    pub fn token(&self) -> Option<SecretString> {
+        self.jwt
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|jwt| jwt.token.clone())
+    }
+
+    /// Expiry of the current token
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.jwt.read().unwrap().as_ref().map(Jwt::expires_at)
+    }
+
+    /// Replace the current token
+    pub fn set_token(&self, token: impl Into<SecretString>) {
+        *self.jwt.write().unwrap() = Some(Jwt::new(token));
+    }
+
+    /// Install the source used to obtain new tokens
+    pub fn set_refresher(&self, refresher: Arc<dyn TokenRefresher>) {
+        *self.refresher.write().unwrap() = Some(refresher);
+    }
+
+    /// Whether the current token is missing or about to expire
+    pub fn needs_refresh(&self) -> bool {
+        self.expires_at()
+            .is_none_or(|expires_at| SystemTime::now() + self.refresh_margin >= expires_at)
+    }
+
+    fn refresher(&self) -> Option<Arc<dyn TokenRefresher>> {
+        self.refresher.read().unwrap().clone()
+    }
+
+    /// Obtain a new token now
+    pub async fn refresh(&self) -> Result<SecretString> {
+        let refresher = self.refresher().ok_or_else(|| {
+            ParadexError::AuthError("No account available to re-authenticate".to_string())
+        })?;
+        let _refreshing = self.refreshing.lock().await;
+        Ok(self.store(refresher.refresh().await?))
+    }
+
+    /// Refresh the token if it is about to expire
+    ///
+    /// Does nothing without a refresher. Concurrent callers share one refresh.
+    pub async fn refresh_if_needed(&self) -> Result<()> {
+        let Some(refresher) = self.refresher() else {
+            return Ok(());
+        };
+        if !self.needs_refresh() {
+            return Ok(());
+        }
+
+        let _refreshing = self.refreshing.lock().await;
+        if self.needs_refresh() {
+            log::debug!("JWT about to expire, refreshing");
+            self.store(refresher.refresh().await?);
+        }
+        Ok(())
+    }
+
+    /// Re-authenticate after `rejected` got a 401
+    ///
+    /// Returns false without a refresher. Skips the refresh when another task
+    /// already replaced the rejected token.
+    pub(crate) async fn refresh_rejected(&self, rejected: Option<&SecretString>) -> Result<bool> {
+        let Some(refresher) = self.refresher() else {
+            return Ok(false);
+        };
+
+        let _refreshing = self.refreshing.lock().await;
+        let current = self.token();
+        let replaced = match (current.as_ref(), rejected) {
+            (Some(current), Some(rejected)) => current.expose_secret() != rejected.expose_secret(),
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if !replaced {
+            log::info!("JWT rejected, re-authenticating");
+            self.store(refresher.refresh().await?);
+        }
+        Ok(true)
+    }
+
+    fn store(&self, token: SecretString) -> SecretString {
+        self.set_token(token.clone());
+        token
+    }
+
+    /// Keep the token fresh in the background until the session is dropped
+    pub fn spawn_refresh_task(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let session = Arc::downgrade(self);
+        tokio::spawn(refresh_loop(session))
+    }
+}
+
+async fn refresh_loop(session: Weak<AuthSession>) {
+    loop {
+        let wait = {
+            let Some(session) = session.upgrade() else {
+                return;
+            };
+            session
+                .expires_at()
+                .and_then(|expires_at| expires_at.checked_sub(session.refresh_margin))
+                .and_then(|refresh_at| refresh_at.duration_since(SystemTime::now()).ok())
+                .unwrap_or_default()
+                .max(MIN_REFRESH_INTERVAL)
+        };
+        tokio::time::sleep(wait).await;
+
+        let result = match session.upgrade() {
+            Some(session) => session.refresh_if_needed().await,
+            None => return,
+        };
+        if let Err(e) = result {
+            log::warn!("Background JWT refresh failed: {e}");
+            tokio::time::sleep(REFRESH_RETRY_DELAY).await;
+        }
+    }
+}
+
+impl std::fmt::Debug for AuthSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthSession")
+            .field("has_token", &self.jwt.read().unwrap().is_some())
+            .field("expires_at", &self.expires_at())
+            .field("can_refresh", &self.refresher().is_some())
+            .field("refresh_margin", &self.refresh_margin)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Counter(AtomicU32);
+
+    #[async_trait]
+    impl TokenRefresher for Counter {
+        async fn refresh(&self) -> Result<SecretString> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("token-{n}").into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_refreshed_once() {
+        let session = AuthSession::default();
+        assert!(!session.refresh_rejected(None).await.unwrap());
+
+        let counter = Arc::new(Counter(AtomicU32::new(0)));
+        session.set_refresher(counter.clone());
+        session.set_token("token-0");
+        let rejected = session.token();
+
+        // Concurrent 401s with the same token share one re-authentication
+        assert!(session.refresh_rejected(rejected.as_ref()).await.unwrap());
+        assert!(session.refresh_rejected(rejected.as_ref()).await.unwrap());
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert_eq!(session.token().unwrap().expose_secret(), "token-1");
+    }
+
+    #[test]
+    fn test_opaque_token_gets_default_lifetime() {
+        let session = AuthSession::default();
+        assert!(session.needs_refresh());
+
+        session.set_token("opaque");
+        let expires_at = session.expires_at().unwrap();
+        assert!(expires_at > SystemTime::now() + DEFAULT_LIFETIME - Duration::from_secs(5));
+        assert!(!session.needs_refresh());
+        assert!(!format!("{session:?}").contains("opaque"));
+    }
+}
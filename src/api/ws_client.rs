@@ -1,4 +1,5 @@
 use crate::{environment::Environment, error::Result};
+use std::time::Duration;
 
 #[path = "ws_client_impl.rs"]
 mod ws_impl;
@@ -94,6 +95,24 @@ impl WebSocketChannel {
     }
 }
 
+/// Connection settings for the WebSocket client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketConfig {
+    pub url: String,
+    /// Interval between keep-alive pings, `None` disables them
+    pub ping_interval: Option<Duration>,
+}
+
+impl WebSocketConfig {
+    /// Default settings for `env`: 20s pings
+    pub fn new(env: Environment) -> Self {
+        Self {
+            url: env.ws_url(),
+            ping_interval: Some(Duration::from_secs(20)),
+        }
+    }
+}
+
 /// WebSocket client facade (wraps implementation)
 pub struct WebSocketClient {
     inner: WebSocketClientImpl,
@@ -107,11 +126,23 @@ impl WebSocketClient {
         }
     }
 
+    /// Create a WebSocket client with custom connection settings
+    pub fn with_config(config: WebSocketConfig) -> Self {
+        Self {
+            inner: WebSocketClientImpl::with_config(config),
+        }
+    }
+
     /// Set JWT token for authenticated channels
-    pub fn set_token(&mut self, token: impl Into<String>) {
+    pub fn set_token(&self, token: impl Into<crate::secret::SecretString>) {
         self.inner.set_token(token);
     }
 
+    /// Read the JWT from a session shared with the REST client
+    pub fn set_auth_session(&mut self, session: std::sync::Arc<crate::api::AuthSession>) {
+        self.inner.set_auth_session(session);
+    }
+
     /// Connect to WebSocket
     pub async fn connect(&self) -> Result<()> {
         self.inner.connect().await
@@ -174,12 +205,12 @@ impl WebSocketClient {
         self.inner.get_subscriptions().await
     }
 
-    /// Manually pump one message (for deterministic consumption)
+    /// Wait briefly for one message to be dispatched (for deterministic consumption)
     pub async fn pump_once(&self) -> Result<bool> {
         self.inner.pump_once().await
     }
 
-    /// Pump messages until predicate is satisfied
+    /// Wait for dispatched messages until one satisfies the predicate
     pub async fn pump_until<F>(&self, predicate: F, timeout_secs: f64) -> Result<u32>
     where
         F: Fn(&serde_json::Value) -> bool,
@@ -210,6 +241,98 @@ mod tests {
         assert_eq!(channel, "bbo.BTC-USD-PERP");
     }
 
+    #[tokio::test]
+    async fn test_connect_is_idempotent() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let accepted = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = std::sync::Arc::clone(&accepted);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while ws.next().await.is_some() {}
+                });
+            }
+        });
+
+        let client = WebSocketClient::with_config(WebSocketConfig {
+            url,
+            ping_interval: None,
+        });
+        client.connect().await.unwrap();
+        // A second call must not open another connection with its own reader
+        client.connect().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+        assert!(client.is_connected().await);
+        client.close().await.unwrap();
+
+        // Connecting after close opens a new connection
+        client.connect().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+        client.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pump_until_sees_dispatched_messages() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await.unwrap().unwrap();
+            let notification = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "subscription",
+                "params": { "channel": "bbo.BTC-USD-PERP", "data": { "bid": "1" } },
+            });
+            loop {
+                let message = Message::Text(notification.to_string());
+                if ws.send(message).await.is_err() {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        });
+
+        let client = WebSocketClient::with_config(WebSocketConfig {
+            url,
+            ping_interval: None,
+        });
+        client.connect().await.unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        client
+            .subscribe(WebSocketChannel::BBO, Some("BTC-USD-PERP"), move |params| {
+                let tx = tx.clone();
+                Box::pin(async move {
+                    let _ = tx.send(params);
+                })
+            })
+            .await
+            .unwrap();
+
+        // The reader task holds no lock while waiting, so pumping isn't blocked
+        let start = std::time::Instant::now();
+        let seen = client
+            .pump_until(|m| m["params"]["channel"] == "bbo.BTC-USD-PERP", 5.0)
+            .await
+            .unwrap();
+        assert!(seen >= 1);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(rx.recv().await.unwrap()["data"]["bid"], "1");
+        client.close().await.unwrap();
+    }
+
     #[test]
     fn test_channel_requires_auth() {
         assert!(WebSocketChannel::Account.requires_auth());
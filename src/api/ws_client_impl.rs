@@ -1,18 +1,27 @@
+use super::WebSocketConfig;
 use crate::{
+    api::session::AuthSession,
     environment::Environment,
     error::{ParadexError, Result},
+    secret::SecretString,
+};
+use futures::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
 };
-use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::net::TcpStream;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::{sleep, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
 
 type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type WsWriter = SplitSink<WsStream, Message>;
+type WsReader = SplitStream<WsStream>;
 type MessageCallback =
     Arc<dyn Fn(serde_json::Value) -> futures::future::BoxFuture<'static, ()> + Send + Sync>;
 
@@ -37,97 +46,120 @@ struct WsResponse {
     error: Option<serde_json::Value>,
 }
 
+/// Background tasks serving the open connection
+struct ConnectionTasks {
+    reader: JoinHandle<()>,
+    ping: Option<JoinHandle<()>>,
+}
+
+impl ConnectionTasks {
+    fn abort(&self) {
+        self.reader.abort();
+        if let Some(ping) = &self.ping {
+            ping.abort();
+        }
+    }
+}
+
 /// WebSocket client implementation with full channel support
+///
+/// Clones share the connection, callbacks and subscriptions.
+#[derive(Clone)]
 pub struct WebSocketClientImpl {
-    ws_url: String,
-    jwt_token: Option<String>,
-    ws_stream: Arc<Mutex<Option<WsStream>>>,
+    config: WebSocketConfig,
+    session: Arc<AuthSession>,
+    writer: Arc<Mutex<Option<WsWriter>>>,
+    /// Messages dispatched by the reader task, for `pump_once` / `pump_until`
+    messages: broadcast::Sender<serde_json::Value>,
     callbacks: Arc<RwLock<HashMap<String, MessageCallback>>>,
     subscribed_channels: Arc<RwLock<HashMap<String, bool>>>,
     next_id: Arc<Mutex<u64>>,
     is_connected: Arc<Mutex<bool>>,
-    auto_reconnect: bool,
-    ping_interval: Option<Duration>,
+    tasks: Arc<Mutex<Option<ConnectionTasks>>>,
 }
 
 impl WebSocketClientImpl {
     /// Create a new WebSocket client
     pub fn new(env: Environment) -> Self {
+        Self::with_config(WebSocketConfig::new(env))
+    }
+
+    /// Create a WebSocket client with custom connection settings
+    pub fn with_config(config: WebSocketConfig) -> Self {
         Self {
-            ws_url: env.ws_url(),
-            jwt_token: None,
-            ws_stream: Arc::new(Mutex::new(None)),
+            config,
+            session: Arc::default(),
+            writer: Arc::new(Mutex::new(None)),
+            messages: broadcast::channel(256).0,
             callbacks: Arc::new(RwLock::new(HashMap::new())),
             subscribed_channels: Arc::new(RwLock::new(HashMap::new())),
             next_id: Arc::new(Mutex::new(1)),
             is_connected: Arc::new(Mutex::new(false)),
-            auto_reconnect: true,
-            ping_interval: Some(Duration::from_secs(20)),
+            tasks: Arc::new(Mutex::new(None)),
         }
     }
 
     /// Set JWT token for authenticated channels
-    pub fn set_token(&mut self, token: impl Into<String>) {
-        self.jwt_token = Some(token.into());
+    pub fn set_token(&self, token: impl Into<SecretString>) {
+        self.session.set_token(token);
+    }
+
+    /// Read the JWT from a session shared with the REST client
+    pub fn set_auth_session(&mut self, session: Arc<AuthSession>) {
+        self.session = session;
     }
 
     /// Connect to WebSocket
+    ///
+    /// Does nothing while a connection is already being served.
     pub async fn connect(&self) -> Result<()> {
-        let url = &self.ws_url;
-        let (ws_stream, _) = connect_async(url)
-            .await
-            .map_err(|e| ParadexError::WebSocketError(format!("Connection failed: {e}")))?;
+        let mut tasks = self.tasks.lock().await;
+        if tasks.as_ref().is_some_and(|t| !t.reader.is_finished()) {
+            return Ok(());
+        }
+        if let Some(stale) = tasks.take() {
+            stale.abort();
+        }
 
-        *self.ws_stream.lock().await = Some(ws_stream);
-        *self.is_connected.lock().await = true;
+        let reader = self.open().await?;
 
-        // Authenticate if we have a token
-        if let Some(token) = &self.jwt_token {
-            self.send_auth(token).await?;
-        }
+        // The reader task owns the read half, so no lock is held while waiting
+        let client = self.clone();
+        let reader = tokio::spawn(async move { client.read_messages(reader).await });
 
-        // Start message reader in background
-        let stream_clone = Arc::clone(&self.ws_stream);
-        let callbacks_clone = Arc::clone(&self.callbacks);
-        let is_connected_clone = Arc::clone(&self.is_connected);
-        let auto_reconnect = self.auto_reconnect;
-
-        tokio::spawn(async move {
-            Self::read_messages(
-                stream_clone,
-                callbacks_clone,
-                is_connected_clone,
-                auto_reconnect,
-            )
-            .await;
+        // Start ping task if configured
+        let ping = self.config.ping_interval.map(|interval| {
+            let client = self.clone();
+            tokio::spawn(async move { client.ping_loop(interval).await })
         });
 
-        // Start ping task if configured
-        if let Some(interval) = self.ping_interval {
-            let stream_clone = Arc::clone(&self.ws_stream);
-            let is_connected_clone = Arc::clone(&self.is_connected);
+        *tasks = Some(ConnectionTasks { reader, ping });
+        log::info!("WebSocket connected to {}", self.config.url);
+        Ok(())
+    }
 
-            tokio::spawn(async move {
-                Self::ping_loop(stream_clone, is_connected_clone, interval).await;
-            });
-        }
+    /// Open the connection and authenticate with the session's current token,
+    /// returning the read half
+    async fn open(&self) -> Result<WsReader> {
+        let (ws_stream, _) = connect_async(&self.config.url)
+            .await
+            .map_err(|e| ParadexError::WebSocketError(format!("Connection failed: {e}")))?;
 
-        log::info!("WebSocket connected to {url}");
-        Ok(())
+        let (writer, reader) = ws_stream.split();
+        *self.writer.lock().await = Some(writer);
+        *self.is_connected.lock().await = true;
+
+        // Authenticate if we have a token
+        if let Some(token) = self.session.token() {
+            self.send_auth(token.expose_secret()).await?;
+        }
+        Ok(reader)
     }
 
     /// Send authentication message
     async fn send_auth(&self, token: &str) -> Result<()> {
-        let auth_msg = json!({
-            "id": self.get_next_id().await,
-            "jsonrpc": "2.0",
-            "method": "auth",
-            "params": {
-                "bearer": token
-            }
-        });
-
-        self.send_message(&auth_msg).await?;
+        self.send_request("auth", json!({ "bearer": token }))
+            .await?;
         log::info!("Sent authentication message");
         Ok(())
     }
@@ -141,16 +173,8 @@ impl WebSocketClientImpl {
             .insert(channel.to_string(), callback);
 
         // Send subscription message
-        let sub_msg = json!({
-            "id": self.get_next_id().await,
-            "jsonrpc": "2.0",
-            "method": "subscribe",
-            "params": {
-                "channel": channel
-            }
-        });
-
-        self.send_message(&sub_msg).await?;
+        self.send_request("subscribe", json!({ "channel": channel }))
+            .await?;
         self.subscribed_channels
             .write()
             .await
@@ -162,16 +186,8 @@ impl WebSocketClientImpl {
 
     /// Unsubscribe from a channel
     pub async fn unsubscribe(&self, channel: &str) -> Result<()> {
-        let unsub_msg = json!({
-            "id": self.get_next_id().await,
-            "jsonrpc": "2.0",
-            "method": "unsubscribe",
-            "params": {
-                "channel": channel
-            }
-        });
-
-        self.send_message(&unsub_msg).await?;
+        self.send_request("unsubscribe", json!({ "channel": channel }))
+            .await?;
         self.subscribed_channels.write().await.remove(channel);
         self.callbacks.write().await.remove(channel);
 
@@ -179,12 +195,23 @@ impl WebSocketClientImpl {
         Ok(())
     }
 
+    /// Send a JSON-RPC request with the next request id
+    async fn send_request(&self, method: &str, params: serde_json::Value) -> Result<()> {
+        let request = WsRequest {
+            id: self.get_next_id().await,
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        };
+        self.send_message(&request).await
+    }
+
     /// Send a message to the WebSocket
-    async fn send_message(&self, message: &serde_json::Value) -> Result<()> {
+    async fn send_message<T: Serialize>(&self, message: &T) -> Result<()> {
         let msg_str = serde_json::to_string(message)?;
-        let mut stream = self.ws_stream.lock().await;
+        let mut writer = self.writer.lock().await;
 
-        if let Some(ws) = stream.as_mut() {
+        if let Some(ws) = writer.as_mut() {
             ws.send(Message::Text(msg_str))
                 .await
                 .map_err(|e| ParadexError::WebSocketError(format!("Send failed: {e}")))?;
@@ -194,79 +221,66 @@ impl WebSocketClientImpl {
         }
     }
 
-    /// Read messages from WebSocket in a loop
-    async fn read_messages(
-        stream: Arc<Mutex<Option<WsStream>>>,
-        callbacks: Arc<RwLock<HashMap<String, MessageCallback>>>,
-        is_connected: Arc<Mutex<bool>>,
-        _auto_reconnect: bool,
-    ) {
+    /// Dispatch messages to callbacks until the connection ends
+    async fn read_messages(self, mut reader: WsReader) {
         loop {
-            let mut stream_guard = stream.lock().await;
-
-            if let Some(ws) = stream_guard.as_mut() {
-                match ws.next().await {
-                    Some(Ok(Message::Text(text))) => {
-                        drop(stream_guard);
-
-                        if let Ok(response) = serde_json::from_str::<WsResponse>(&text) {
-                            if let Some(params) = response.params {
-                                if let Some(channel) =
-                                    params.get("channel").and_then(|v| v.as_str())
-                                {
-                                    let callbacks_read = callbacks.read().await;
-                                    if let Some(callback) = callbacks_read.get(channel) {
-                                        let callback_clone = Arc::clone(callback);
-                                        let params_clone = params.clone();
-                                        tokio::spawn(async move {
-                                            callback_clone(params_clone).await;
-                                        });
-                                    }
-                                }
-                            }
-                        }
+            match reader.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    self.dispatch(&text).await;
+                    if let Ok(message) = serde_json::from_str(&text) {
+                        // Nobody pumping is not an error
+                        let _ = self.messages.send(message);
                     }
-                    Some(Ok(Message::Close(_))) => {
-                        log::info!("WebSocket closed");
-                        *is_connected.lock().await = false;
-                        break;
-                    }
-                    Some(Err(e)) => {
-                        log::error!("WebSocket error: {e}");
-                        *is_connected.lock().await = false;
-                        break;
-                    }
-                    None => {
-                        log::info!("WebSocket stream ended");
-                        *is_connected.lock().await = false;
-                        break;
+                }
+                Some(Ok(Message::Close(_))) => {
+                    log::info!("WebSocket closed");
+                    break;
+                }
+                Some(Err(e)) => {
+                    log::error!("WebSocket error: {e}");
+                    break;
+                }
+                None => {
+                    log::info!("WebSocket stream ended");
+                    break;
+                }
+                _ => {}
+            }
+        }
+        *self.is_connected.lock().await = false;
+    }
+
+    /// Pass a notification to the callback registered for its channel
+    async fn dispatch(&self, text: &str) {
+        if let Ok(response) = serde_json::from_str::<WsResponse>(text) {
+            if let Some(params) = response.params {
+                if let Some(channel) = params.get("channel").and_then(|v| v.as_str()) {
+                    let callbacks_read = self.callbacks.read().await;
+                    if let Some(callback) = callbacks_read.get(channel) {
+                        let callback_clone = Arc::clone(callback);
+                        let params_clone = params.clone();
+                        tokio::spawn(async move {
+                            callback_clone(params_clone).await;
+                        });
                     }
-                    _ => {}
                 }
-            } else {
-                break;
             }
         }
     }
 
     /// Ping loop to keep connection alive
-    async fn ping_loop(
-        stream: Arc<Mutex<Option<WsStream>>>,
-        is_connected: Arc<Mutex<bool>>,
-        interval: Duration,
-    ) {
+    async fn ping_loop(self, interval: Duration) {
         loop {
             sleep(interval).await;
 
-            if !*is_connected.lock().await {
+            if !*self.is_connected.lock().await {
                 break;
             }
 
-            let mut stream_guard = stream.lock().await;
-            if let Some(ws) = stream_guard.as_mut() {
+            let mut writer = self.writer.lock().await;
+            if let Some(ws) = writer.as_mut() {
                 if ws.send(Message::Ping(vec![])).await.is_err() {
                     log::error!("Failed to send ping");
-                    break;
                 }
             }
         }
@@ -287,13 +301,15 @@ impl WebSocketClientImpl {
 
     /// Close the WebSocket connection
     pub async fn close(&self) -> Result<()> {
-        let mut stream = self.ws_stream.lock().await;
-        if let Some(ws) = stream.as_mut() {
-            ws.close(None)
+        if let Some(tasks) = self.tasks.lock().await.take() {
+            tasks.abort();
+        }
+        *self.is_connected.lock().await = false;
+        if let Some(mut ws) = self.writer.lock().await.take() {
+            ws.close()
                 .await
                 .map_err(|e| ParadexError::WebSocketError(format!("Close failed: {e}")))?;
         }
-        *self.is_connected.lock().await = false;
         Ok(())
     }
 
@@ -302,60 +318,35 @@ impl WebSocketClientImpl {
         self.subscribed_channels.read().await.clone()
     }
 
-    /// Manually pump one message from the WebSocket (for deterministic consumption)
+    /// Wait briefly for the reader to dispatch a message (for deterministic consumption)
     pub async fn pump_once(&self) -> Result<bool> {
-        let mut stream_guard = self.ws_stream.lock().await;
-
-        if let Some(ws) = stream_guard.as_mut() {
-            match tokio::time::timeout(Duration::from_millis(1), ws.next()).await {
-                Ok(Some(Ok(Message::Text(text)))) => {
-                    drop(stream_guard);
-
-                    if let Ok(response) = serde_json::from_str::<WsResponse>(&text) {
-                        if let Some(params) = response.params {
-                            if let Some(channel) = params.get("channel").and_then(|v| v.as_str()) {
-                                let callbacks_read = self.callbacks.read().await;
-                                if let Some(callback) = callbacks_read.get(channel) {
-                                    let callback_clone = Arc::clone(callback);
-                                    let params_clone = params.clone();
-                                    tokio::spawn(async move {
-                                        callback_clone(params_clone).await;
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    Ok(true)
-                }
-                Ok(None) | Err(_) => Ok(false),
-                _ => Ok(false),
-            }
-        } else {
-            Ok(false)
-        }
+        let mut messages = self.messages.subscribe();
+        let received = tokio::time::timeout(Duration::from_millis(1), messages.recv()).await;
+        Ok(matches!(received, Ok(Ok(_))))
     }
 
-    /// Pump messages until predicate is satisfied or timeout
+    /// Wait for dispatched messages until one satisfies the predicate or the
+    /// timeout elapses, returning how many were seen
     pub async fn pump_until<F>(&self, predicate: F, timeout_secs: f64) -> Result<u32>
     where
         F: Fn(&serde_json::Value) -> bool,
     {
-        let start = std::time::Instant::now();
-        let timeout = Duration::from_secs_f64(timeout_secs);
+        let deadline = tokio::time::Instant::now() + Duration::from_secs_f64(timeout_secs);
+        let mut messages = self.messages.subscribe();
         let mut count = 0u32;
-        let last_message: Option<serde_json::Value> = None;
-
-        while start.elapsed() < timeout {
-            if self.pump_once().await? {
-                count += 1;
-                // Check predicate on last received message
-                if let Some(msg) = &last_message {
-                    if predicate(msg) {
+
+        while let Ok(received) = tokio::time::timeout_at(deadline, messages.recv()).await {
+            match received {
+                Ok(message) => {
+                    count += 1;
+                    if predicate(&message) {
                         break;
                     }
                 }
-            } else {
-                tokio::time::sleep(Duration::from_millis(1)).await;
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    count = count.saturating_add(skipped as u32);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
             }
         }
 
@@ -364,20 +355,7 @@ impl WebSocketClientImpl {
 
     /// Inject a message into the processing pipeline (for testing/simulation)
     pub async fn inject(&self, message: &str) -> Result<()> {
-        if let Ok(response) = serde_json::from_str::<WsResponse>(message) {
-            if let Some(params) = response.params {
-                if let Some(channel) = params.get("channel").and_then(|v| v.as_str()) {
-                    let callbacks_read = self.callbacks.read().await;
-                    if let Some(callback) = callbacks_read.get(channel) {
-                        let callback_clone = Arc::clone(callback);
-                        let params_clone = params.clone();
-                        tokio::spawn(async move {
-                            callback_clone(params_clone).await;
-                        });
-                    }
-                }
-            }
-        }
+        self.dispatch(message).await;
         Ok(())
     }
 }
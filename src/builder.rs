@@ -0,0 +1,403 @@
+//! Builder for fully configured [`Paradex`] clients
+
+use crate::{
+    api::{
+        ApiClient, AuthSession, HttpClient, Middleware, RateLimitConfig, RetryPolicy,
+        WebSocketClient, WebSocketConfig,
+    },
+    environment::Environment,
+    error::Result,
+    secret::SecretString,
+    signer::Signer,
+    types::OnboardingOptions,
+    Paradex,
+};
+use std::{sync::Arc, time::Duration};
+
+/// Timeout applied to whole HTTP requests unless configured otherwise
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Credentials the client authenticates with
+pub(crate) enum Credentials {
+    /// Ethereum key the L2 key is derived from
+    L1 {
+        address: String,
+        private_key: SecretString,
+    },
+    /// Starknet key given directly
+    L2 {
+        address: String,
+        private_key: SecretString,
+    },
+    /// Starknet key held by a signer
+    Signer {
+        address: String,
+        signer: Arc<dyn Signer>,
+    },
+}
+
+/// Configures the HTTP, WebSocket, auth, retry and logging options of a
+/// [`Paradex`] client
+///
+/// Created with [`Paradex::builder`]. Without credentials, [`build`] returns a
+/// client for public endpoints only.
+///
+/// [`build`]: ParadexBuilder::build
+pub struct ParadexBuilder {
+    env: Environment,
+    api_url: Option<String>,
+    http_client: Option<reqwest::Client>,
+    timeout: Duration,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<reqwest::Proxy>,
+    ws_url: Option<String>,
+    ws_ping_interval: Option<Option<Duration>>,
+    refresh_margin: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limits: Option<RateLimitConfig>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    log_level: Option<log::LevelFilter>,
+    credentials: Option<Credentials>,
+    onboarding: OnboardingOptions,
+}
+
+impl Default for ParadexBuilder {
+    fn default() -> Self {
+        Self {
+            env: Environment::Testnet,
+            api_url: None,
+            http_client: None,
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            user_agent: None,
+            proxy: None,
+            ws_url: None,
+            ws_ping_interval: None,
+            refresh_margin: None,
+            retry_policy: None,
+            rate_limits: None,
+            middlewares: Vec::new(),
+            log_level: None,
+            credentials: None,
+            onboarding: OnboardingOptions::default(),
+        }
+    }
+}
+
+impl ParadexBuilder {
+    /// Environment providing the default URLs, testnet unless set
+    pub fn environment(mut self, env: Environment) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// REST API base URL, overriding the environment's
+    pub fn api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = Some(api_url.into());
+        self
+    }
+
+    /// WebSocket URL, overriding the environment's
+    pub fn ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
+
+    /// Send requests through this client
+    ///
+    /// The timeout, connect timeout, user agent and proxy options are ignored,
+    /// configure them on `client` instead.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Timeout for whole HTTP requests, 30 seconds by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Timeout for establishing HTTP connections
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// `User-Agent` header sent with every HTTP request
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Route HTTP requests through a proxy
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Interval between WebSocket pings, `None` disables them
+    pub fn ws_ping_interval(mut self, interval: Option<Duration>) -> Self {
+        self.ws_ping_interval = Some(interval);
+        self
+    }
+
+    /// How long before its expiry the JWT is refreshed, one minute by default
+    pub fn auth_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = Some(margin);
+        self
+    }
+
+    /// Retry policy for transient HTTP failures
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Client side rate limits
+    pub fn rate_limits(mut self, rate_limits: RateLimitConfig) -> Self {
+        self.rate_limits = Some(rate_limits);
+        self
+    }
+
+    /// Run every HTTP request through `middleware`, after the ones added
+    /// before it
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Install a logger printing to stderr at `level`
+    ///
+    /// Does nothing if the application already installed a logger.
+    pub fn log_level(mut self, level: log::LevelFilter) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Authenticate with an Ethereum key, deriving the L2 key
+    pub fn l1_credentials(
+        mut self,
+        l1_address: impl Into<String>,
+        l1_private_key: impl Into<SecretString>,
+    ) -> Self {
+        self.credentials = Some(Credentials::L1 {
+            address: l1_address.into(),
+            private_key: l1_private_key.into(),
+        });
+        self
+    }
+
+    /// Authenticate with a Starknet key given directly
+    pub fn l2_credentials(
+        mut self,
+        l1_address: impl Into<String>,
+        l2_private_key: impl Into<SecretString>,
+    ) -> Self {
+        self.credentials = Some(Credentials::L2 {
+            address: l1_address.into(),
+            private_key: l2_private_key.into(),
+        });
+        self
+    }
+
+    /// Authenticate with a Starknet key held by `signer`
+    pub fn signer(mut self, l1_address: impl Into<String>, signer: Arc<dyn Signer>) -> Self {
+        self.credentials = Some(Credentials::Signer {
+            address: l1_address.into(),
+            signer,
+        });
+        self
+    }
+
+    /// Referral, marketing and UTM codes sent if the account is onboarded by
+    /// [`build`](ParadexBuilder::build)
+    pub fn onboarding(mut self, options: OnboardingOptions) -> Self {
+        self.onboarding = options;
+        self
+    }
+
+    /// Create the client, onboarding and authenticating when credentials
+    /// were given
+    pub async fn build(self) -> Result<Paradex> {
+        if let Some(level) = self.log_level {
+            // Fails only when a logger is already installed, which is fine
+            let _ = env_logger::Builder::new().filter_level(level).try_init();
+        }
+
+        let client = match self.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder().timeout(self.timeout);
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        let env = self.env;
+        let mut http_client =
+            HttpClient::with_client(client, self.api_url.unwrap_or_else(|| env.api_url()));
+        if let Some(margin) = self.refresh_margin {
+            http_client.set_auth_session(Arc::new(AuthSession::with_refresh_margin(margin)));
+        }
+        if let Some(retry_policy) = self.retry_policy {
+            http_client.set_retry_policy(retry_policy);
+        }
+        if let Some(rate_limits) = self.rate_limits {
+            http_client.set_rate_limits(rate_limits);
+        }
+        for middleware in self.middlewares {
+            http_client.add_middleware(middleware);
+        }
+
+        let mut ws_config = WebSocketConfig::new(env.clone());
+        if let Some(ws_url) = self.ws_url {
+            ws_config.url = ws_url;
+        }
+        if let Some(ping_interval) = self.ws_ping_interval {
+            ws_config.ping_interval = ping_interval;
+        }
+
+        let paradex = Paradex::from_clients(
+            env,
+            ApiClient::with_http_client(http_client),
+            WebSocketClient::with_config(ws_config),
+        );
+        match self.credentials {
+            Some(credentials) => paradex.login(credentials, self.onboarding).await,
+            None => Ok(paradex),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+    use serde_json::json;
+
+    fn system_config() -> String {
+        json!({
+            "l1_chain_id": "1",
+            "starknet_chain_id": "SN_MAIN",
+            "starknet_fullnode_rpc_url": "http://localhost",
+            "paraclear_address": "0x123",
+            "paraclear_account_proxy_hash": "0x1234567890abcdef",
+            "paraclear_account_hash": "0xabcdef1234567890",
+            "paraclear_decimals": 8,
+            "bridged_tokens": [],
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_build_with_l2_credentials() {
+        let mut server = mockito::Server::new_async().await;
+        let config = server
+            .mock("GET", "/system/config")
+            .match_header("user-agent", "paradex-bot/1.0")
+            .with_body(system_config())
+            .create_async()
+            .await;
+        server
+            .mock("GET", Matcher::Regex("^/onboarding".into()))
+            .with_body(r#"{"exists":true}"#)
+            .create_async()
+            .await;
+        let auth = server
+            .mock("POST", Matcher::Regex("^/auth/0x[0-9a-f]+$".into()))
+            .match_header("user-agent", "paradex-bot/1.0")
+            .with_body(r#"{"jwt_token":"eyJhbGciOiJIUzI1NiJ9.built"}"#)
+            .create_async()
+            .await;
+
+        let paradex = Paradex::builder()
+            .environment(Environment::Prod)
+            .api_url(server.url())
+            .user_agent("paradex-bot/1.0")
+            .timeout(Duration::from_secs(5))
+            .ws_ping_interval(None)
+            .retry_policy(RetryPolicy::none())
+            .l2_credentials("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb", "0x1234")
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(paradex.environment(), Environment::Prod);
+        assert_eq!(paradex.api_client().api_url(), server.url());
+        let account = paradex.account().unwrap();
+        assert_eq!(
+            account.get_jwt_token().unwrap().expose_secret(),
+            "eyJhbGciOiJIUzI1NiJ9.built"
+        );
+        assert_eq!(
+            paradex.auth_session().token().unwrap().expose_secret(),
+            "eyJhbGciOiJIUzI1NiJ9.built"
+        );
+        config.assert_async().await;
+        auth.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_build_onboards_with_referral_code() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/system/config")
+            .with_body(system_config())
+            .create_async()
+            .await;
+        server
+            .mock("GET", Matcher::Regex("^/onboarding".into()))
+            .with_body(r#"{"exists":false}"#)
+            .create_async()
+            .await;
+        let create = server
+            .mock("POST", "/onboarding")
+            .match_body(Matcher::PartialJson(json!({"referral_code": "friend"})))
+            .with_body("{}")
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("POST", Matcher::Regex("^/auth/0x[0-9a-f]+$".into()))
+            .with_body(r#"{"jwt_token":"eyJhbGciOiJIUzI1NiJ9.built"}"#)
+            .create_async()
+            .await;
+
+        Paradex::builder()
+            .api_url(server.url())
+            .retry_policy(RetryPolicy::none())
+            .onboarding(OnboardingOptions {
+                referral_code: Some("friend".to_string()),
+                ..Default::default()
+            })
+            .l2_credentials("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb", "0x1234")
+            .build()
+            .await
+            .unwrap();
+
+        create.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_build_without_credentials() {
+        let paradex = Paradex::builder()
+            .api_url("http://localhost:1/v1/")
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(paradex.environment(), Environment::Testnet);
+        assert_eq!(paradex.api_client().api_url(), "http://localhost:1/v1");
+        assert!(paradex.account().is_none());
+    }
+}
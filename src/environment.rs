@@ -1,20 +1,43 @@
 use std::fmt;
 
 /// Paradex environment configuration
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Environment {
     /// Production environment
     Prod,
     /// Testnet environment
     Testnet,
+    /// Nightly build of the exchange, reset frequently
+    Nightly,
+    /// Any other deployment, such as a regional endpoint or a local mock
+    Custom {
+        name: String,
+        api_url: String,
+        ws_url: String,
+    },
 }
 
 impl Environment {
+    /// Custom environment with explicit URLs
+    pub fn custom(
+        name: impl Into<String>,
+        api_url: impl Into<String>,
+        ws_url: impl Into<String>,
+    ) -> Self {
+        Environment::Custom {
+            name: name.into(),
+            api_url: api_url.into(),
+            ws_url: ws_url.into(),
+        }
+    }
+
     /// Get the API base URL for this environment
     pub fn api_url(&self) -> String {
         match self {
             Environment::Prod => "https://api.prod.paradex.trade/v1".to_string(),
             Environment::Testnet => "https://api.testnet.paradex.trade/v1".to_string(),
+            Environment::Nightly => "https://api.nightly.paradex.trade/v1".to_string(),
+            Environment::Custom { api_url, .. } => api_url.clone(),
         }
     }
 
@@ -23,6 +46,8 @@ impl Environment {
         match self {
             Environment::Prod => "wss://ws.api.prod.paradex.trade/v1".to_string(),
             Environment::Testnet => "wss://ws.api.testnet.paradex.trade/v1".to_string(),
+            Environment::Nightly => "wss://ws.api.nightly.paradex.trade/v1".to_string(),
+            Environment::Custom { ws_url, .. } => ws_url.clone(),
         }
     }
 }
@@ -32,6 +57,8 @@ impl fmt::Display for Environment {
         match self {
             Environment::Prod => write!(f, "prod"),
             Environment::Testnet => write!(f, "testnet"),
+            Environment::Nightly => write!(f, "nightly"),
+            Environment::Custom { name, .. } => write!(f, "{name}"),
         }
     }
 }
@@ -50,11 +77,24 @@ mod tests {
             Environment::Prod.api_url(),
             "https://api.prod.paradex.trade/v1"
         );
+        assert_eq!(
+            Environment::Nightly.ws_url(),
+            "wss://ws.api.nightly.paradex.trade/v1"
+        );
+
+        let local = Environment::custom("local", "http://127.0.0.1:8080/v1", "ws://127.0.0.1:8081");
+        assert_eq!(local.api_url(), "http://127.0.0.1:8080/v1");
+        assert_eq!(local.ws_url(), "ws://127.0.0.1:8081");
     }
 
     #[test]
     fn test_environment_display() {
         assert_eq!(Environment::Testnet.to_string(), "testnet");
         assert_eq!(Environment::Prod.to_string(), "prod");
+        assert_eq!(Environment::Nightly.to_string(), "nightly");
+        assert_eq!(
+            Environment::custom("eu-west", "https://a", "wss://b").to_string(),
+            "eu-west"
+        );
     }
 }
@@ -1,3 +1,7 @@
+use crate::api::rate_limit::RateLimitBucket;
+use rust_decimal::Decimal;
+use starknet_types_core::felt::Felt;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type for Paradex operations
@@ -34,9 +38,26 @@ pub enum ParadexError {
     #[error("Invalid configuration: {0}")]
     ConfigError(String),
 
-    /// API error with HTTP status code
-    #[error("API error (status {status}): {message}")]
-    ApiError { status: u16, message: String },
+    /// API error with HTTP status code and the decoded Paradex error envelope
+    #[error("API error (status {status}) on {method} {path}: {}", api_error_text(.code.as_ref(), .message))]
+    ApiError {
+        status: u16,
+        /// `error` field of the envelope, `None` when the body is not an envelope
+        code: Option<ApiErrorCode>,
+        /// `message` field of the envelope, or the raw body
+        message: String,
+        /// `data` field of the envelope
+        data: Option<Box<serde_json::Value>>,
+        method: String,
+        path: String,
+    },
+
+    /// Client side rate limit reached in fail-fast mode
+    #[error("Rate limit reached for {bucket}, retry in {retry_after:?}")]
+    RateLimited {
+        bucket: RateLimitBucket,
+        retry_after: Duration,
+    },
 
     /// Generic error
     #[error("{0}")]
@@ -49,4 +70,221 @@ pub enum ParadexError {
     /// Ethereum error
     #[error("Ethereum error: {0}")]
     EthereumError(String),
+
+    /// Decimal to quantum conversion error
+    #[error("Quantum conversion error: {0}")]
+    QuantumError(#[from] QuantumError),
+
+    /// Signature verification error
+    #[error("Signature verification failed: {0}")]
+    SignatureError(#[from] SignatureError),
+
+    /// Keystore error
+    #[error("Keystore error: {0}")]
+    KeystoreError(#[from] KeystoreError),
+}
+
+impl ParadexError {
+    /// Paradex error code of an API error
+    pub fn api_error_code(&self) -> Option<&ApiErrorCode> {
+        match self {
+            ParadexError::ApiError { code, .. } => code.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether repeating the same call later may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ParadexError::ApiError { status, code, .. } => {
+                matches!(status, 429 | 502 | 503 | 504)
+                    || code.as_ref().is_some_and(ApiErrorCode::is_retryable)
+            }
+            ParadexError::HttpError(e) => e.is_connect() || e.is_timeout(),
+            ParadexError::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the JWT or request signature was rejected
+    pub fn is_auth_error(&self) -> bool {
+        match self {
+            ParadexError::ApiError { status, code, .. } => {
+                *status == 401 || code.as_ref().is_some_and(ApiErrorCode::is_auth_error)
+            }
+            ParadexError::AuthError(_) => true,
+            _ => false,
+        }
+    }
+}
+
+fn api_error_text(code: Option<&ApiErrorCode>, message: &str) -> String {
+    match code {
+        Some(code) => format!("{code}: {message}"),
+        None => message.to_string(),
+    }
+}
+
+macro_rules! api_error_codes {
+    ($($(#[$meta:meta])* $variant:ident => $code:literal,)*) => {
+        /// Error code from the `error` field of a Paradex error response
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ApiErrorCode {
+            $($(#[$meta])* $variant,)*
+            /// Code not known to this SDK version
+            Unknown(String),
+        }
+
+        impl ApiErrorCode {
+            /// Code as sent by the API
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(ApiErrorCode::$variant => $code,)*
+                    ApiErrorCode::Unknown(code) => code,
+                }
+            }
+        }
+
+        impl From<&str> for ApiErrorCode {
+            fn from(code: &str) -> Self {
+                match code {
+                    $($code => ApiErrorCode::$variant,)*
+                    other => ApiErrorCode::Unknown(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+api_error_codes! {
+    ValidationError => "VALIDATION_ERROR",
+    BindingError => "BINDING_ERROR",
+    InternalError => "INTERNAL_ERROR",
+    NotFound => "NOT_FOUND",
+    ServiceUnavailable => "SERVICE_UNAVAILABLE",
+    InvalidRequestParameter => "INVALID_REQUEST_PARAMETER",
+    AccountNotFound => "ACCOUNT_NOT_FOUND",
+    NotOnboarded => "NOT_ONBOARDED",
+    InsufficientMargin => "INSUFFICIENT_MARGIN",
+    InsufficientBalance => "INSUFFICIENT_BALANCE",
+    OrderNotFound => "ORDER_ID_NOT_FOUND",
+    ClientOrderIdNotFound => "CLIENT_ORDER_ID_NOT_FOUND",
+    OrderIsClosed => "ORDER_IS_CLOSED",
+    DuplicatedClientId => "DUPLICATED_CLIENT_ID",
+    InvalidPricePrecision => "INVALID_PRICE_PRECISION",
+    InvalidSizePrecision => "INVALID_SIZE_PRECISION",
+    MarketNotFound => "MARKET_NOT_FOUND",
+    InvalidOrderSignature => "INVALID_ORDER_SIGNATURE",
+    InvalidToken => "INVALID_TOKEN",
+    InvalidStarknetSignature => "INVALID_STARKNET_SIGNATURE",
+    StarknetSignatureVerificationFailed => "STARKNET_SIGNATURE_VERIFICATION_FAILED",
+    InvalidEthereumSignature => "INVALID_ETHEREUM_SIGNATURE",
+    InvalidSignatureExpiration => "INVALID_SIGNATURE_EXPIRATION",
+    InvalidTimestamp => "INVALID_TIMESTAMP",
+}
+
+impl ApiErrorCode {
+    /// Codes reporting a temporary server side condition
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorCode::InternalError | ApiErrorCode::ServiceUnavailable
+        )
+    }
+
+    /// Codes reporting a rejected JWT or authentication signature
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorCode::InvalidToken
+                | ApiErrorCode::InvalidStarknetSignature
+                | ApiErrorCode::StarknetSignatureVerificationFailed
+                | ApiErrorCode::InvalidEthereumSignature
+                | ApiErrorCode::InvalidSignatureExpiration
+        )
+    }
+}
+
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ApiErrorCode {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(ApiErrorCode::from(code.as_str()))
+    }
+}
+
+impl serde::Serialize for ApiErrorCode {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Errors converting a decimal amount into chain quantums
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QuantumError {
+    /// The value is not a valid decimal for the chain
+    #[error("invalid decimal {value:?}: {reason}")]
+    InvalidDecimal { value: String, reason: String },
+
+    /// The value has more decimal places than the quantum allows
+    #[error("{value} has more than {decimals} decimal places")]
+    PrecisionLoss { value: Decimal, decimals: u32 },
+
+    /// The scaled value does not fit in a decimal
+    #[error("{value} overflows when scaled by 10^{decimals}")]
+    Overflow { value: Decimal, decimals: u32 },
+}
+
+/// Errors verifying a Stark signature over typed data
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The signature is not a `[r,s]` pair of felts
+    #[error("malformed signature {signature:?}: {reason}")]
+    Malformed { signature: String, reason: String },
+
+    /// The typed data could not be hashed
+    #[error("cannot hash message: {0}")]
+    Message(String),
+
+    /// The signature is well formed but was not produced by the public key
+    #[error("signature does not match message hash {message_hash:#x}")]
+    Invalid { message_hash: Felt },
+}
+
+/// Errors reading or writing encrypted keystores
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum KeystoreError {
+    /// The keystore file could not be read or written
+    #[error("I/O error: {0}")]
+    Io(String),
+
+    /// The keystore parameters are malformed
+    #[error("invalid parameters: {0}")]
+    InvalidParams(String),
+
+    /// The keystore uses a version, cipher or KDF this SDK does not support
+    #[error("unsupported {0}")]
+    Unsupported(String),
+
+    /// The MAC does not match, usually because the passphrase is wrong
+    #[error("wrong passphrase")]
+    WrongPassphrase,
+
+    /// The keystore holds a different kind of key than requested
+    #[error("{0}")]
+    WrongKind(&'static str),
+
+    /// The decrypted key is not a valid private key
+    #[error("invalid key: {0}")]
+    InvalidKey(String),
 }
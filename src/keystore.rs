@@ -0,0 +1,583 @@
+//! Encrypted key storage
+//!
+//! Reads and writes Ethereum V3 keystores (Web3 Secret Storage) for L1 keys.
+//! Stark keys use the same envelope with `"curve": "stark"`, the Stark public
+//! key and optionally the L1 and L2 addresses of the account.
+
+use crate::{
+    error::{KeystoreError, Result},
+    secret::{SecretKey, SecretString},
+};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use ethers::{signers::LocalWallet, signers::Signer as _, utils::keccak256};
+use hmac::Hmac;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use starknet_types_core::felt::Felt;
+use std::path::Path;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+const KEYSTORE_VERSION: u8 = 3;
+const CIPHER: &str = "aes-128-ctr";
+const STARK_CURVE: &str = "stark";
+
+/// Bounds on KDF parameters read from a file, checked before any memory is
+/// allocated or work is done for them
+const DKLEN_RANGE: std::ops::RangeInclusive<u32> = 32..=64;
+/// scrypt memory use is `128 * n * r` bytes
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+const MAX_SCRYPT_P: u32 = 16;
+const MAX_PBKDF2_ROUNDS: u32 = 10_000_000;
+
+/// V3 keystore file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u8,
+    pub id: String,
+    /// Address owning the key: the L1 address for Ethereum keys, the L2
+    /// account address for Stark keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// `"stark"` for Stark keys, absent for Ethereum keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    /// Stark public key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// L1 address of the Paradex account holding a Stark key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_address: Option<String>,
+    #[serde(alias = "Crypto")]
+    pub crypto: CryptoParams,
+}
+
+/// Encrypted secret and the parameters to decrypt it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CryptoParams {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    #[serde(flatten)]
+    pub kdf: KdfParams,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+/// Key derivation function and its parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum KdfParams {
+    Scrypt {
+        dklen: u32,
+        n: u32,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        c: u32,
+        dklen: u32,
+        prf: String,
+        salt: String,
+    },
+}
+
+/// Key derivation function used when encrypting a new keystore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt with cost `n` (a power of two), block size `r` and parallelism `p`
+    Scrypt { n: u32, r: u32, p: u32 },
+    /// PBKDF2-HMAC-SHA256 with `c` iterations
+    Pbkdf2 { c: u32 },
+}
+
+impl Default for Kdf {
+    /// The scrypt parameters geth uses for new keys
+    fn default() -> Self {
+        Kdf::Scrypt {
+            n: 1 << 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    fn derive_key(&self, passphrase: &str) -> std::result::Result<Vec<u8>, KeystoreError> {
+        self.check()?;
+        match self {
+            KdfParams::Scrypt {
+                dklen,
+                n,
+                r,
+                p,
+                salt,
+            } => {
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p)
+                    .map_err(|e| KeystoreError::InvalidParams(format!("scrypt: {e}")))?;
+                let mut key = vec![0u8; *dklen as usize];
+                scrypt::scrypt(passphrase.as_bytes(), &decode_hex(salt)?, &params, &mut key)
+                    .map_err(|e| KeystoreError::InvalidParams(format!("scrypt: {e}")))?;
+                Ok(key)
+            }
+            KdfParams::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            } => {
+                if prf != "hmac-sha256" {
+                    return Err(KeystoreError::Unsupported(format!("prf {prf}")));
+                }
+                let mut key = vec![0u8; *dklen as usize];
+                pbkdf2::pbkdf2::<Hmac<Sha256>>(
+                    passphrase.as_bytes(),
+                    &decode_hex(salt)?,
+                    *c,
+                    &mut key,
+                );
+                Ok(key)
+            }
+        }
+    }
+
+    /// Reject parameters that are malformed, too weak or too costly
+    fn check(&self) -> std::result::Result<(), KeystoreError> {
+        let invalid = |message: String| Err(KeystoreError::InvalidParams(message));
+        let dklen = match self {
+            KdfParams::Scrypt { dklen, n, r, p, .. } => {
+                if !n.is_power_of_two() || *n < 2 {
+                    return invalid(format!("scrypt n must be a power of two, got {n}"));
+                }
+                if 128 * u64::from(*n) * u64::from(*r) > MAX_SCRYPT_MEMORY {
+                    return invalid(format!("scrypt n = {n}, r = {r} needs too much memory"));
+                }
+                if *r == 0 || *p == 0 || *p > MAX_SCRYPT_P {
+                    return invalid(format!("scrypt r = {r}, p = {p} out of range"));
+                }
+                dklen
+            }
+            KdfParams::Pbkdf2 { c, dklen, .. } => {
+                if !(1..=MAX_PBKDF2_ROUNDS).contains(c) {
+                    return invalid(format!("pbkdf2 c = {c} out of range"));
+                }
+                dklen
+            }
+        };
+        if !DKLEN_RANGE.contains(dklen) {
+            return invalid(format!(
+                "dklen must be between {} and {}, got {dklen}",
+                DKLEN_RANGE.start(),
+                DKLEN_RANGE.end()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Keystore {
+    /// Read a keystore from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| KeystoreError::Io(format!("{}: {e}", path.display())))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write the keystore as JSON, readable only by the owner where supported
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = serde_json::to_string_pretty(self)?;
+        write_private(path, contents.as_bytes())
+            .map_err(|e| KeystoreError::Io(format!("{}: {e}", path.display())).into())
+    }
+
+    /// Whether this keystore holds a Stark key
+    pub fn is_stark(&self) -> bool {
+        self.curve.as_deref() == Some(STARK_CURVE)
+    }
+
+    /// Encrypt `secret` with `passphrase`
+    pub fn encrypt(secret: &[u8], passphrase: &str, kdf: Kdf) -> Result<Self> {
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+
+        let salt = hex::encode(salt);
+        let kdf = match kdf {
+            Kdf::Scrypt { n, r, p } => KdfParams::Scrypt {
+                dklen: 32,
+                n,
+                r,
+                p,
+                salt,
+            },
+            Kdf::Pbkdf2 { c } => KdfParams::Pbkdf2 {
+                c,
+                dklen: 32,
+                prf: "hmac-sha256".to_string(),
+                salt,
+            },
+        };
+
+        let derived_key = Zeroizing::new(kdf.derive_key(passphrase)?);
+        let mut ciphertext = secret.to_vec();
+        Aes128Ctr::new(derived_key[..16].into(), (&iv).into()).apply_keystream(&mut ciphertext);
+        let mac = keccak256([&derived_key[16..32], &ciphertext[..]].concat());
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            id: random_uuid(&mut rng),
+            address: None,
+            curve: None,
+            public_key: None,
+            l1_address: None,
+            crypto: CryptoParams {
+                cipher: CIPHER.to_string(),
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf,
+                mac: hex::encode(mac),
+            },
+        })
+    }
+
+    /// Decrypt the secret with `passphrase`
+    pub fn decrypt(&self, passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::Unsupported(format!("version {}", self.version)).into());
+        }
+        if self.crypto.cipher != CIPHER {
+            return Err(
+                KeystoreError::Unsupported(format!("cipher {}", self.crypto.cipher)).into(),
+            );
+        }
+
+        let derived_key = Zeroizing::new(self.crypto.kdf.derive_key(passphrase)?);
+
+        let mut plaintext = Zeroizing::new(decode_hex(&self.crypto.ciphertext)?);
+        let mac = keccak256([&derived_key[16..32], &plaintext[..]].concat());
+        let expected_mac = decode_hex(&self.crypto.mac)?;
+        if !bool::from(mac.as_slice().ct_eq(&expected_mac)) {
+            return Err(KeystoreError::WrongPassphrase.into());
+        }
+
+        let iv: [u8; 16] = decode_hex(&self.crypto.cipherparams.iv)?
+            .try_into()
+            .map_err(|_| KeystoreError::InvalidParams("iv must be 16 bytes".into()))?;
+        Aes128Ctr::new(derived_key[..16].into(), (&iv).into()).apply_keystream(&mut plaintext);
+
+        Ok(plaintext)
+    }
+
+    /// Decrypt the Ethereum private key this keystore holds
+    pub fn eth_key(&self, passphrase: &str) -> Result<EthKey> {
+        if self.is_stark() {
+            return Err(
+                KeystoreError::WrongKind("expected an Ethereum key, found a Stark key").into(),
+            );
+        }
+
+        let private_key =
+            SecretString::new(format!("0x{}", hex::encode(self.decrypt(passphrase)?)));
+        let wallet: LocalWallet = private_key
+            .expose_secret()
+            .parse()
+            .map_err(|e| KeystoreError::InvalidKey(format!("{e}")))?;
+
+        Ok(EthKey {
+            address: ethers::utils::to_checksum(&wallet.address(), None),
+            private_key,
+        })
+    }
+
+    /// Decrypt the Stark private key this keystore holds
+    pub fn stark_key(&self, passphrase: &str) -> Result<StarkKey> {
+        if !self.is_stark() {
+            return Err(
+                KeystoreError::WrongKind("expected a Stark key, found an Ethereum key").into(),
+            );
+        }
+
+        let secret = self.decrypt(passphrase)?;
+        if secret.len() != 32 {
+            return Err(KeystoreError::InvalidKey("Stark key must be 32 bytes".into()).into());
+        }
+        let private_key = SecretKey::from_felt(Felt::from_bytes_be_slice(&secret));
+        let public_key = private_key.public_key();
+
+        if let Some(expected) = &self.public_key {
+            let expected = Felt::from_hex(expected)
+                .map_err(|e| KeystoreError::InvalidKey(format!("public key: {e}")))?;
+            if expected != public_key {
+                return Err(KeystoreError::InvalidKey("public key mismatch".into()).into());
+            }
+        }
+
+        Ok(StarkKey {
+            private_key,
+            public_key,
+            address: self.address.clone(),
+            l1_address: self.l1_address.clone(),
+        })
+    }
+}
+
+/// Ethereum key decrypted from a keystore
+#[derive(Debug, Clone)]
+pub struct EthKey {
+    /// Checksummed L1 address
+    pub address: String,
+    /// Hex encoded private key
+    pub private_key: SecretString,
+}
+
+/// Stark key decrypted from a keystore
+#[derive(Clone)]
+pub struct StarkKey {
+    pub private_key: SecretKey,
+    pub public_key: Felt,
+    /// L2 account address, if recorded in the keystore
+    pub address: Option<String>,
+    /// L1 address of the account, if recorded in the keystore
+    pub l1_address: Option<String>,
+}
+
+impl std::fmt::Debug for StarkKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StarkKey")
+            .field("private_key", &self.private_key)
+            .field("public_key", &format!("{:#x}", self.public_key))
+            .field("address", &self.address)
+            .field("l1_address", &self.l1_address)
+            .finish()
+    }
+}
+
+/// Decrypt an Ethereum V3 keystore file
+pub fn load_eth_key(path: impl AsRef<Path>, passphrase: &str) -> Result<EthKey> {
+    Keystore::load(path)?.eth_key(passphrase)
+}
+
+/// Encrypt an Ethereum private key into a V3 keystore file
+pub fn save_eth_key(
+    path: impl AsRef<Path>,
+    private_key: &SecretString,
+    passphrase: &str,
+    kdf: Kdf,
+) -> Result<()> {
+    let wallet: LocalWallet = private_key
+        .expose_secret()
+        .parse()
+        .map_err(|e| KeystoreError::InvalidKey(format!("{e}")))?;
+
+    let secret = Zeroizing::new(wallet.signer().to_bytes());
+    let mut keystore = Keystore::encrypt(&secret, passphrase, kdf)?;
+    keystore.address = Some(hex::encode(wallet.address()));
+    keystore.save(path)
+}
+
+/// Decrypt a Stark keystore file
+pub fn load_stark_key(path: impl AsRef<Path>, passphrase: &str) -> Result<StarkKey> {
+    Keystore::load(path)?.stark_key(passphrase)
+}
+
+/// Encrypt a Stark private key into a keystore file
+///
+/// `address` is the L2 account address and `l1_address` the L1 address of
+/// the Paradex account; both are stored in clear text for reference.
+pub fn save_stark_key(
+    path: impl AsRef<Path>,
+    private_key: &SecretKey,
+    address: Option<&str>,
+    l1_address: Option<&str>,
+    passphrase: &str,
+    kdf: Kdf,
+) -> Result<()> {
+    let secret = Zeroizing::new(private_key.expose_secret().to_bytes_be());
+    let mut keystore = Keystore::encrypt(&*secret, passphrase, kdf)?;
+    keystore.curve = Some(STARK_CURVE.to_string());
+    keystore.public_key = Some(format!("{:#x}", private_key.public_key()));
+    keystore.address = address.map(str::to_string);
+    keystore.l1_address = l1_address.map(str::to_string);
+    keystore.save(path)
+}
+
+fn decode_hex(value: &str) -> std::result::Result<Vec<u8>, KeystoreError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| KeystoreError::InvalidParams(format!("invalid hex {value:?}: {e}")))
+}
+
+fn random_uuid(rng: &mut impl RngCore) -> String {
+    let mut bytes = [0u8; 16];
+    rng.fill_bytes(&mut bytes);
+    // Version 4, RFC 4122 variant
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?
+            .write_all(contents)
+    }
+    #[cfg(not(unix))]
+    {
+        std::fs::write(path, contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParadexError;
+
+    /// Test vector from the Web3 Secret Storage definition
+    const PBKDF2_KEYSTORE: &str = include_str!("../tests/vectors/keystore_pbkdf2.json");
+    /// The same key encrypted with geth-style scrypt parameters by Python's hashlib
+    const SCRYPT_KEYSTORE: &str = include_str!("../tests/vectors/keystore_scrypt.json");
+    const VECTOR_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    const FAST_KDF: Kdf = Kdf::Scrypt {
+        n: 1024,
+        r: 8,
+        p: 1,
+    };
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("paradex-{}-{name}.json", std::process::id()))
+    }
+
+    #[test]
+    fn test_decrypt_known_pbkdf2_file() {
+        let keystore: Keystore = serde_json::from_str(PBKDF2_KEYSTORE).unwrap();
+        let secret = keystore.decrypt("testpassword").unwrap();
+        assert_eq!(hex::encode(&*secret), VECTOR_KEY);
+    }
+
+    #[test]
+    fn test_decrypt_known_scrypt_file() {
+        let keystore: Keystore = serde_json::from_str(SCRYPT_KEYSTORE).unwrap();
+        let secret = keystore.decrypt("testpassword").unwrap();
+        assert_eq!(hex::encode(&*secret), VECTOR_KEY);
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let keystore: Keystore = serde_json::from_str(PBKDF2_KEYSTORE).unwrap();
+        assert!(matches!(
+            keystore.decrypt("wrong"),
+            Err(ParadexError::KeystoreError(KeystoreError::WrongPassphrase))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_kdf_params() {
+        let base: serde_json::Value = serde_json::from_str(PBKDF2_KEYSTORE).unwrap();
+        let cases = [
+            serde_json::json!({"c": 0}),
+            serde_json::json!({"dklen": 16}),
+            serde_json::json!({"dklen": u32::MAX}),
+        ];
+        for patch in cases {
+            let mut value = base.clone();
+            let params = value["crypto"]["kdfparams"].as_object_mut().unwrap();
+            params.extend(patch.as_object().unwrap().clone());
+            let keystore: Keystore = serde_json::from_value(value).unwrap();
+            assert!(matches!(
+                keystore.decrypt("testpassword"),
+                Err(ParadexError::KeystoreError(KeystoreError::InvalidParams(_)))
+            ));
+        }
+
+        let scrypt = KdfParams::Scrypt {
+            dklen: 32,
+            n: 1 << 30,
+            r: 8,
+            p: 1,
+            salt: String::new(),
+        };
+        assert!(matches!(
+            scrypt.derive_key("testpassword"),
+            Err(KeystoreError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_eth_key_roundtrip() {
+        let path = temp_path("eth");
+        let private_key = SecretString::new(format!("0x{VECTOR_KEY}"));
+        save_eth_key(&path, &private_key, "secret", Kdf::Pbkdf2 { c: 1024 }).unwrap();
+
+        let key = load_eth_key(&path, "secret").unwrap();
+        assert_eq!(key.private_key.expose_secret(), private_key.expose_secret());
+        assert_eq!(
+            key.address.to_lowercase(),
+            "0x008aeeda4d805471df9b2a5b0f38a0c3bcba786b"
+        );
+        assert!(!format!("{key:?}").contains(VECTOR_KEY));
+        assert!(matches!(
+            load_stark_key(&path, "secret"),
+            Err(ParadexError::KeystoreError(KeystoreError::WrongKind(_)))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_stark_key_roundtrip() {
+        let path = temp_path("stark");
+        let private_key = SecretKey::from_hex("0x1234abcd").unwrap();
+        save_stark_key(
+            &path,
+            &private_key,
+            Some("0x5678"),
+            Some("0x70997970C51812dc3A010C7d01b50e0d17dc79C8"),
+            "secret",
+            FAST_KDF,
+        )
+        .unwrap();
+
+        let keystore = Keystore::load(&path).unwrap();
+        assert!(keystore.is_stark());
+        assert!(!keystore.crypto.ciphertext.contains("1234abcd"));
+
+        let key = load_stark_key(&path, "secret").unwrap();
+        assert_eq!(key.private_key.expose_secret(), private_key.expose_secret());
+        assert_eq!(key.public_key, private_key.public_key());
+        assert!(!format!("{key:?}").contains("1234abcd"));
+        assert_eq!(key.address.as_deref(), Some("0x5678"));
+        assert!(load_stark_key(&path, "wrong").is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -31,35 +31,44 @@
 
 pub mod account;
 pub mod api;
+pub mod builder;
 pub mod constants;
 pub mod environment;
 pub mod error;
+pub mod keystore;
 pub mod message;
+pub mod secret;
+pub mod signer;
 pub mod subkey;
 pub mod types;
 pub mod utils;
 
 pub use api::WebSocketChannel;
+pub use builder::ParadexBuilder;
 pub use environment::Environment;
-pub use error::{ParadexError, Result};
+pub use error::{ApiErrorCode, ParadexError, Result};
+pub use secret::{SecretKey, SecretString};
+pub use signer::Signer;
 pub use subkey::{ParadexSubkey, SubkeyAccount};
 pub use types::*;
 
 use account::ParadexAccount;
-use api::{authenticate, needs_refresh, onboard, ApiClient, WebSocketClient};
-use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use api::{authenticate, onboard, ApiClient, AuthSession, TokenRefresher, WebSocketClient};
+use builder::Credentials;
+use std::sync::{Arc, Weak};
 
 /// Main Paradex client for interacting with the Paradex API
 ///
-/// This is the primary entry point for using the Paradex SDK.
+/// This is the primary entry point for using the Paradex SDK. Clones are
+/// cheap and share the same clients, account and JWT, so one client can be
+/// handed to many tasks.
+#[derive(Clone)]
 pub struct Paradex {
     env: Environment,
-    api_client: Arc<Mutex<ApiClient>>,
-    ws_client: Arc<Mutex<WebSocketClient>>,
-    account: Option<Arc<Mutex<ParadexAccount>>>,
-    config: Option<SystemConfig>,
-    auth_timestamp: Arc<Mutex<Option<SystemTime>>>,
+    api_client: Arc<ApiClient>,
+    ws_client: Arc<WebSocketClient>,
+    account: Option<Arc<ParadexAccount>>,
+    config: Option<Arc<SystemConfig>>,
 }
 
 impl Paradex {
@@ -81,17 +90,86 @@ impl Paradex {
     /// }
     /// ```
     pub fn new(env: Environment) -> Result<Self> {
-        let api_client = Arc::new(Mutex::new(ApiClient::new(env)?));
-        let ws_client = Arc::new(Mutex::new(WebSocketClient::new(env)));
+        Ok(Self::from_clients(
+            env.clone(),
+            ApiClient::new(env.clone())?,
+            WebSocketClient::new(env),
+        ))
+    }
 
-        Ok(Self {
+    /// Configure every client option before connecting
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use paradex_rs::{Paradex, Environment};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let paradex = Paradex::builder()
+    ///         .environment(Environment::Testnet)
+    ///         .timeout(Duration::from_secs(10))
+    ///         .user_agent("my-bot/1.0")
+    ///         .l2_credentials("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb", "0x1234...")
+    ///         .build()
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn builder() -> ParadexBuilder {
+        ParadexBuilder::default()
+    }
+
+    /// Create a client from configured REST and WebSocket clients
+    ///
+    /// The WebSocket client reads its JWT from the REST client's session.
+    fn from_clients(
+        env: Environment,
+        api_client: ApiClient,
+        mut ws_client: WebSocketClient,
+    ) -> Self {
+        ws_client.set_auth_session(api_client.auth_session());
+        Self {
             env,
-            api_client,
-            ws_client,
+            api_client: Arc::new(api_client),
+            ws_client: Arc::new(ws_client),
             account: None,
             config: None,
-            auth_timestamp: Arc::new(Mutex::new(None)),
-        })
+        }
+    }
+
+    /// Create the account for `credentials`, then onboard and authenticate
+    async fn login(
+        mut self,
+        credentials: Credentials,
+        onboarding: OnboardingOptions,
+    ) -> Result<Self> {
+        // Fetch system config first
+        let config = self.fetch_and_store_config().await?;
+
+        let account = match credentials {
+            Credentials::L1 {
+                address,
+                private_key,
+            } => ParadexAccount::from_l1_private_key(&config, address, private_key).await?,
+            Credentials::L2 {
+                address,
+                private_key,
+            } => {
+                let l2_key = SecretKey::from_hex(private_key.expose_secret())?;
+                ParadexAccount::from_l2_private_key(&config, address, l2_key)?
+            }
+            Credentials::Signer { address, signer } => {
+                ParadexAccount::from_signer(&config, address, signer)?
+            }
+        };
+        self.account = Some(Arc::new(account));
+
+        // Perform authentication flow
+        self.authenticate(onboarding).await?;
+
+        Ok(self)
     }
 
     /// Initialize Paradex client with L1 credentials (derives L2 key)
@@ -120,22 +198,15 @@ impl Paradex {
     pub async fn with_l1_credentials(
         env: Environment,
         l1_address: impl Into<String>,
-        l1_private_key: impl Into<String>,
+        l1_private_key: impl Into<SecretString>,
     ) -> Result<Self> {
-        let mut paradex = Self::new(env)?;
-
-        // Fetch system config first
-        let config = paradex.fetch_and_store_config().await?;
-
-        // Create account from L1 credentials
-        let account =
-            ParadexAccount::from_l1_private_key(&config, l1_address, l1_private_key).await?;
-        paradex.account = Some(Arc::new(Mutex::new(account)));
-
-        // Perform authentication flow
-        paradex.authenticate().await?;
-
-        Ok(paradex)
+        let credentials = Credentials::L1 {
+            address: l1_address.into(),
+            private_key: l1_private_key.into(),
+        };
+        Self::new(env)?
+            .login(credentials, OnboardingOptions::default())
+            .await
     }
 
     /// Initialize with L2 credentials directly
@@ -148,159 +219,208 @@ impl Paradex {
     pub async fn with_l2_credentials(
         env: Environment,
         l1_address: impl Into<String>,
-        l2_private_key: impl Into<String>,
+        l2_private_key: impl Into<SecretString>,
     ) -> Result<Self> {
-        let mut paradex = Self::new(env)?;
-
-        // Fetch system config first
-        let config = paradex.fetch_and_store_config().await?;
-
-        // Parse L2 private key
-        use starknet_types_core::felt::Felt;
-        let l2_key = Felt::from_hex(&l2_private_key.into())
-            .map_err(|e| ParadexError::ConfigError(format!("Invalid L2 key: {e}")))?;
-
-        // Create account from L2 credentials
-        let account = ParadexAccount::from_l2_private_key(&config, l1_address, l2_key)?;
-        paradex.account = Some(Arc::new(Mutex::new(account)));
+        let credentials = Credentials::L2 {
+            address: l1_address.into(),
+            private_key: l2_private_key.into(),
+        };
+        Self::new(env)?
+            .login(credentials, OnboardingOptions::default())
+            .await
+    }
 
-        // Perform authentication flow
-        paradex.authenticate().await?;
+    /// Initialize with an L2 key held by `signer`
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Environment
+    /// * `l1_address` - Ethereum address
+    /// * `signer` - Signer holding the L2 (Starknet) key
+    pub async fn with_signer(
+        env: Environment,
+        l1_address: impl Into<String>,
+        signer: Arc<dyn Signer>,
+    ) -> Result<Self> {
+        let credentials = Credentials::Signer {
+            address: l1_address.into(),
+            signer,
+        };
+        Self::new(env)?
+            .login(credentials, OnboardingOptions::default())
+            .await
+    }
 
-        Ok(paradex)
+    /// Initialize from an encrypted keystore file
+    ///
+    /// Ethereum V3 keystores are used as L1 credentials. Stark keystores are
+    /// used as L2 credentials and must record the account's L1 address. A
+    /// recorded L2 address must match the account derived from the key.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use paradex_rs::{Paradex, Environment};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let paradex =
+    ///         Paradex::from_keystore(Environment::Testnet, "keystore.json", "passphrase").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn from_keystore(
+        env: Environment,
+        path: impl AsRef<std::path::Path>,
+        passphrase: &str,
+    ) -> Result<Self> {
+        let keystore = keystore::Keystore::load(path)?;
+        if keystore.is_stark() {
+            let key = keystore.stark_key(passphrase)?;
+            let l1_address = key.l1_address.ok_or_else(|| {
+                ParadexError::ConfigError("Stark keystore has no L1 address".to_string())
+            })?;
+            let signer = Arc::new(signer::InMemorySigner::new(key.private_key));
+            let mut paradex = Self::new(env)?;
+            let config = paradex.fetch_and_store_config().await?;
+            let account = ParadexAccount::from_signer(&config, l1_address, signer)?;
+            if let Some(address) = &key.address {
+                let recorded = starknet_types_core::felt::Felt::from_hex(address).map_err(|e| {
+                    ParadexError::ConfigError(format!("Invalid keystore address: {e}"))
+                })?;
+                if recorded != account.l2_address {
+                    return Err(ParadexError::ConfigError(format!(
+                        "Keystore address {address} does not match derived account {}",
+                        account.l2_address_hex()
+                    )));
+                }
+            }
+            paradex.account = Some(Arc::new(account));
+            paradex.authenticate(OnboardingOptions::default()).await?;
+            Ok(paradex)
+        } else {
+            let key = keystore.eth_key(passphrase)?;
+            Self::with_l1_credentials(env, key.address, key.private_key).await
+        }
     }
 
     /// Get the environment this client is using
     pub fn environment(&self) -> Environment {
-        self.env
+        self.env.clone()
     }
 
     /// Get a reference to the API client (for public endpoints)
-    pub fn api_client(&self) -> Arc<Mutex<ApiClient>> {
+    pub fn api_client(&self) -> Arc<ApiClient> {
         Arc::clone(&self.api_client)
     }
 
     /// Get a reference to the WebSocket client
-    pub fn ws_client(&self) -> Arc<Mutex<WebSocketClient>> {
+    pub fn ws_client(&self) -> Arc<WebSocketClient> {
         Arc::clone(&self.ws_client)
     }
 
     /// Get account reference
-    pub fn account(&self) -> Option<Arc<Mutex<ParadexAccount>>> {
+    pub fn account(&self) -> Option<Arc<ParadexAccount>> {
         self.account.as_ref().map(Arc::clone)
     }
 
     /// Fetch and store system configuration
-    #[allow(clippy::await_holding_lock)]
-    async fn fetch_and_store_config(&mut self) -> Result<SystemConfig> {
-        let config = {
-            let api_client = self.api_client.lock().unwrap();
-            api_client.fetch_system_config().await?
-        };
-        self.config = Some(config.clone());
+    async fn fetch_and_store_config(&mut self) -> Result<Arc<SystemConfig>> {
+        let config = Arc::new(self.api_client.fetch_system_config().await?);
+        self.config = Some(Arc::clone(&config));
         Ok(config)
     }
 
-    /// Perform onboarding and authentication
-    async fn authenticate(&self) -> Result<()> {
-        let _account = self
+    /// Perform onboarding with `onboarding` and authentication
+    ///
+    /// Installs the account as the session's token source, so the JWT is
+    /// refreshed in the background and after a 401 from then on.
+    async fn authenticate(&self, onboarding: OnboardingOptions) -> Result<()> {
+        let account = self
             .account
             .as_ref()
             .ok_or_else(|| ParadexError::AuthError("No account initialized".to_string()))?;
 
-        // Step 1: Onboarding (may fail if already onboarded, that's ok)
-        let _ = self.onboard().await;
+        // Step 1: Onboarding, skipped by the API side check when already done
+        self.onboard(onboarding).await?;
 
-        // Step 2: Authentication to get JWT
-        self.auth().await?;
+        // Step 2: Authentication to get JWT, shared by the REST and WS clients
+        let session = self.auth_session();
+        session.set_refresher(Arc::new(AccountRefresher {
+            account: Arc::clone(account),
+            api_client: Arc::downgrade(&self.api_client),
+        }));
+        session.refresh().await?;
+        session.spawn_refresh_task();
 
         Ok(())
     }
 
-    /// Perform onboarding
-    #[allow(clippy::await_holding_lock)]
-    async fn onboard(&self) -> Result<()> {
+    /// Onboard the account, checking the onboarding status first
+    ///
+    /// The authenticating constructors already onboard new accounts, without
+    /// referral or marketing codes; pass those with [`ParadexBuilder::onboarding`]
+    /// instead. Called afterwards, this reports
+    /// [`OnboardingOutcome::AlreadyOnboarded`].
+    pub async fn onboard(&self, options: OnboardingOptions) -> Result<OnboardingOutcome> {
         let account = self
             .account
             .as_ref()
             .ok_or_else(|| ParadexError::AuthError("No account initialized".to_string()))?;
 
-        let account_guard = account.lock().unwrap();
-        let headers = account_guard.onboarding_headers()?;
-        let public_key_hex = account_guard.l2_public_key_hex();
-        drop(account_guard);
-
-        // Get HTTP client
-        let client = {
-            let api_client = self.api_client.lock().unwrap();
-            api_client.get_http_client()
-        };
-
-        let api_url = self.env.api_url();
-
+        let headers = account.onboarding_headers().await?;
+        let public_key_hex = account.l2_public_key_hex();
         // Call onboarding API
-        onboard(&client, &api_url, headers, &public_key_hex).await?;
-        log::info!("Onboarding successful for: {public_key_hex}");
-
-        Ok(())
+        let outcome = onboard(
+            self.api_client.http_client(),
+            headers,
+            &public_key_hex,
+            &options,
+        )
+        .await?;
+        log::info!("Onboarding for {public_key_hex}: {outcome:?}");
+
+        Ok(outcome)
     }
 
-    /// Authenticate to get JWT token
-    #[allow(clippy::await_holding_lock)]
-    async fn auth(&self) -> Result<()> {
-        let account = self
-            .account
-            .as_ref()
-            .ok_or_else(|| ParadexError::AuthError("No account initialized".to_string()))?;
-
-        let account_guard = account.lock().unwrap();
-        let headers = account_guard.auth_headers()?;
-        let public_key_hex = account_guard.l2_public_key_hex();
-        drop(account_guard);
-
-        // Get HTTP client
-        let client = {
-            let api_client = self.api_client.lock().unwrap();
-            api_client.get_http_client()
-        };
-
-        let api_url = self.env.api_url();
-
-        // Call auth API and get JWT
-        let jwt_token = authenticate(&client, &api_url, headers, &public_key_hex).await?;
-        log::info!("Authentication successful for: {public_key_hex}");
-
-        // Store JWT in account
-        let mut account_guard = account.lock().unwrap();
-        account_guard.set_jwt_token(&jwt_token);
-        drop(account_guard);
-
-        // Store JWT in API client
-        let mut api_client = self.api_client.lock().unwrap();
-        api_client.set_token(&jwt_token);
-
-        // Update auth timestamp
-        *self.auth_timestamp.lock().unwrap() = Some(SystemTime::now());
-
-        Ok(())
+    /// Session holding the JWT shared by the REST and WebSocket clients
+    pub fn auth_session(&self) -> Arc<AuthSession> {
+        self.api_client.auth_session()
     }
 
-    /// Refresh JWT token if needed
+    /// Refresh JWT token if it is about to expire
+    ///
+    /// Private calls and the background task already do this, calling it is
+    /// only needed to refresh ahead of time.
     pub async fn refresh_auth_if_needed(&self) -> Result<()> {
-        if self.account.is_none() {
-            return Ok(());
-        }
+        self.auth_session().refresh_if_needed().await
+    }
+}
 
-        let auth_time = *self.auth_timestamp.lock().unwrap();
+/// Obtains a JWT by signing an auth request with the account's key
+///
+/// Holds the API client weakly, the client owns the session owning this.
+struct AccountRefresher {
+    account: Arc<ParadexAccount>,
+    api_client: Weak<ApiClient>,
+}
 
-        if let Some(timestamp) = auth_time {
-            if needs_refresh(timestamp) {
-                log::info!("JWT token expired, refreshing...");
-                self.auth().await?;
-            }
-        }
+#[async_trait::async_trait]
+impl TokenRefresher for AccountRefresher {
+    async fn refresh(&self) -> Result<SecretString> {
+        let api_client = self
+            .api_client
+            .upgrade()
+            .ok_or_else(|| ParadexError::AuthError("Client was dropped".to_string()))?;
+        let headers = self.account.auth_headers().await?;
+        let public_key_hex = self.account.l2_public_key_hex();
+
+        let jwt_token = authenticate(api_client.http_client(), headers, &public_key_hex).await?;
+        log::info!("Authentication successful for: {public_key_hex}");
 
-        Ok(())
+        // Keep the account's copy in sync with the session
+        self.account.set_jwt_token(jwt_token.clone());
+        Ok(jwt_token)
     }
 }
 
@@ -314,6 +434,93 @@ mod tests {
         assert!(paradex.is_ok());
     }
 
+    #[test]
+    fn test_handles_are_shareable() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+        assert_shareable::<Paradex>();
+        assert_shareable::<ParadexSubkey>();
+        assert_shareable::<Arc<ParadexAccount>>();
+    }
+
+    #[test]
+    fn test_clones_share_session() {
+        let paradex = Paradex::new(Environment::Testnet).unwrap();
+        let clone = paradex.clone();
+        paradex
+            .api_client()
+            .set_token("eyJhbGciOiJIUzI1NiJ9.shared");
+
+        assert!(Arc::ptr_eq(&paradex.auth_session(), &clone.auth_session()));
+        assert_eq!(
+            clone.auth_session().token().unwrap().expose_secret(),
+            "eyJhbGciOiJIUzI1NiJ9.shared"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_custom_environment_urls() {
+        let mut server = mockito::Server::new_async().await;
+        let time = server
+            .mock("GET", "/system/time")
+            .with_body(r#"{"server_time":1700000000000}"#)
+            .create_async()
+            .await;
+
+        let env = Environment::custom("local", server.url(), "ws://127.0.0.1:1");
+        let paradex = Paradex::new(env.clone()).unwrap();
+        assert_eq!(paradex.environment(), env);
+        assert_eq!(paradex.api_client().api_url(), server.url());
+
+        paradex.api_client().fetch_system_time().await.unwrap();
+        time.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_from_keystore_rejects_mismatched_address() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/system/config")
+            .with_body(
+                serde_json::json!({
+                    "l1_chain_id": "1",
+                    "starknet_chain_id": "SN_MAIN",
+                    "starknet_fullnode_rpc_url": "http://localhost",
+                    "paraclear_address": "0x123",
+                    "paraclear_account_proxy_hash": "0x1234567890abcdef",
+                    "paraclear_account_hash": "0xabcdef1234567890",
+                    "paraclear_decimals": 8,
+                    "bridged_tokens": [],
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let auth = server
+            .mock("POST", mockito::Matcher::Regex("^/auth".into()))
+            .expect(0)
+            .create_async()
+            .await;
+
+        let path =
+            std::env::temp_dir().join(format!("paradex-{}-mismatch.json", std::process::id()));
+        keystore::save_stark_key(
+            &path,
+            &SecretKey::from_hex("0x1234").unwrap(),
+            Some("0xdead"),
+            Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb"),
+            "secret",
+            keystore::Kdf::Pbkdf2 { c: 1024 },
+        )
+        .unwrap();
+
+        let env = Environment::custom("local", server.url(), "ws://127.0.0.1:1");
+        let result = Paradex::from_keystore(env, &path, "secret").await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(ParadexError::ConfigError(_))));
+        auth.assert_async().await;
+    }
+
     #[test]
     fn test_environment() {
         let paradex = Paradex::new(Environment::Testnet).unwrap();
@@ -11,18 +11,9 @@ pub fn build_auth_message(chain_id: Felt, timestamp: i64, expiry: i64) -> TypedD
     types.insert(
         "StarkNetDomain".to_string(),
         vec![
-            TypeMember {
-                name: "name".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "chainId".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("name", "felt"),
+            TypeMember::new("chainId", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
@@ -30,14 +21,8 @@ pub fn build_auth_message(chain_id: Felt, timestamp: i64, expiry: i64) -> TypedD
     types.insert(
         "Auth".to_string(),
         vec![
-            TypeMember {
-                name: "timestamp".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "expiry".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("timestamp", "felt"),
+            TypeMember::new("expiry", "felt"),
         ],
     );
 
@@ -53,6 +38,7 @@ pub fn build_auth_message(chain_id: Felt, timestamp: i64, expiry: i64) -> TypedD
             name: "Paradex".to_string(),
             chain_id: format!("{chain_id:#x}"),
             version: "1".to_string(),
+            revision: None,
         },
         primary_type: "Auth".to_string(),
         types,
@@ -73,40 +59,19 @@ pub fn build_fullnode_message(
     types.insert(
         "StarkNetDomain".to_string(),
         vec![
-            TypeMember {
-                name: "name".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "chainId".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("name", "felt"),
+            TypeMember::new("chainId", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
     types.insert(
         "FullnodeRequest".to_string(),
         vec![
-            TypeMember {
-                name: "account".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "payload".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "timestamp".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("account", "felt"),
+            TypeMember::new("payload", "felt"),
+            TypeMember::new("timestamp", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
@@ -133,6 +98,7 @@ pub fn build_fullnode_message(
             name: "Paradex".to_string(),
             chain_id: format!("{chain_id:#x}"),
             version: "1".to_string(),
+            revision: None,
         },
         primary_type: "FullnodeRequest".to_string(),
         types,
@@ -11,36 +11,18 @@ pub fn build_block_trade_message(chain_id: Felt, block_trade: &BlockTradeRequest
     types.insert(
         "StarkNetDomain".to_string(),
         vec![
-            TypeMember {
-                name: "name".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "chainId".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("name", "felt"),
+            TypeMember::new("chainId", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
     types.insert(
         "BlockTrade".to_string(),
         vec![
-            TypeMember {
-                name: "timestamp".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "markets".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "required_signers".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("timestamp", "felt"),
+            TypeMember::new("markets", "felt*"),
+            TypeMember::new("required_signers", "felt*"),
         ],
     );
 
@@ -51,11 +33,11 @@ pub fn build_block_trade_message(chain_id: Felt, block_trade: &BlockTradeRequest
     );
     message.insert(
         "markets".to_string(),
-        Value::String(block_trade.markets.join(",")),
+        Value::from(block_trade.markets.clone()),
     );
     message.insert(
         "required_signers".to_string(),
-        Value::String(block_trade.required_signers.join(",")),
+        Value::from(block_trade.required_signers.clone()),
     );
 
     TypedData {
@@ -63,6 +45,7 @@ pub fn build_block_trade_message(chain_id: Felt, block_trade: &BlockTradeRequest
             name: "Paradex".to_string(),
             chain_id: format!("{chain_id:#x}"),
             version: "1".to_string(),
+            revision: None,
         },
         primary_type: "BlockTrade".to_string(),
         types,
@@ -77,27 +60,15 @@ pub fn build_block_offer_message(chain_id: Felt, offer: &BlockOfferRequest) -> T
     types.insert(
         "StarkNetDomain".to_string(),
         vec![
-            TypeMember {
-                name: "name".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "chainId".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("name", "felt"),
+            TypeMember::new("chainId", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
     types.insert(
         "BlockOffer".to_string(),
-        vec![TypeMember {
-            name: "timestamp".to_string(),
-            type_name: "felt".to_string(),
-        }],
+        vec![TypeMember::new("timestamp", "felt")],
     );
 
     let mut message = HashMap::new();
@@ -111,6 +82,7 @@ pub fn build_block_offer_message(chain_id: Felt, offer: &BlockOfferRequest) -> T
             name: "Paradex".to_string(),
             chain_id: format!("{chain_id:#x}"),
             version: "1".to_string(),
+            revision: None,
         },
         primary_type: "BlockOffer".to_string(),
         types,
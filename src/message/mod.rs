@@ -8,8 +8,11 @@ pub mod onboarding;
 pub mod order;
 pub mod typed_data;
 
+#[cfg(test)]
+pub(crate) mod vectors;
+
 pub use auth::{build_auth_message, build_fullnode_message};
 pub use block_trades::{build_block_offer_message, build_block_trade_message};
 pub use onboarding::build_onboarding_message;
 pub use order::{build_modify_order_message, build_order_message};
-pub use typed_data::TypedData;
+pub use typed_data::{Revision, TypeMember, TypedData};
@@ -10,18 +10,9 @@ pub fn build_onboarding_message(chain_id: Felt) -> TypedData {
     types.insert(
         "StarkNetDomain".to_string(),
         vec![
-            TypeMember {
-                name: "name".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "chainId".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("name", "felt"),
+            TypeMember::new("chainId", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
@@ -35,6 +26,7 @@ pub fn build_onboarding_message(chain_id: Felt) -> TypedData {
             name: "Paradex".to_string(),
             chain_id: format!("{chain_id:#x}"),
             version: "1".to_string(),
+            revision: None,
         },
         primary_type: "Onboarding".to_string(),
         types,
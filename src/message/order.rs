@@ -1,4 +1,5 @@
 use crate::{
+    error::Result,
     message::typed_data::{Domain, TypeMember, TypedData},
     types::Order,
 };
@@ -7,25 +8,19 @@ use starknet_types_core::felt::Felt;
 use std::collections::HashMap;
 
 /// Build order message for signing
-pub fn build_order_message(chain_id: Felt, order: &Order) -> TypedData {
+///
+/// Size and price are signed in quantums, so this fails if either cannot be
+/// represented exactly with `PARACLEAR_DECIMALS` decimals.
+pub fn build_order_message(chain_id: Felt, order: &Order) -> Result<TypedData> {
     let mut types = HashMap::new();
 
     // Define StarkNetDomain type
     types.insert(
         "StarkNetDomain".to_string(),
         vec![
-            TypeMember {
-                name: "name".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "chainId".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("name", "felt"),
+            TypeMember::new("chainId", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
@@ -33,30 +28,12 @@ pub fn build_order_message(chain_id: Felt, order: &Order) -> TypedData {
     types.insert(
         "Order".to_string(),
         vec![
-            TypeMember {
-                name: "timestamp".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "market".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "side".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "orderType".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "size".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "price".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("timestamp", "felt"),
+            TypeMember::new("market", "felt"),
+            TypeMember::new("side", "felt"),
+            TypeMember::new("orderType", "felt"),
+            TypeMember::new("size", "felt"),
+            TypeMember::new("price", "felt"),
         ],
     );
 
@@ -74,41 +51,33 @@ pub fn build_order_message(chain_id: Felt, order: &Order) -> TypedData {
         "orderType".to_string(),
         Value::String(order.order_type.to_string()),
     );
-    message.insert("size".to_string(), Value::String(order.chain_size()));
-    message.insert("price".to_string(), Value::String(order.chain_price()));
+    message.insert("size".to_string(), Value::String(order.chain_size()?));
+    message.insert("price".to_string(), Value::String(order.chain_price()?));
 
-    TypedData {
+    Ok(TypedData {
         domain: Domain {
             name: "Paradex".to_string(),
             chain_id: format!("{chain_id:#x}"),
             version: "1".to_string(),
+            revision: None,
         },
         primary_type: "Order".to_string(),
         types,
         message,
-    }
+    })
 }
 
 /// Build modify order message for signing
-pub fn build_modify_order_message(chain_id: Felt, order: &Order) -> TypedData {
+pub fn build_modify_order_message(chain_id: Felt, order: &Order) -> Result<TypedData> {
     let mut types = HashMap::new();
 
     // Define StarkNetDomain type
     types.insert(
         "StarkNetDomain".to_string(),
         vec![
-            TypeMember {
-                name: "name".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "chainId".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "version".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("name", "felt"),
+            TypeMember::new("chainId", "felt"),
+            TypeMember::new("version", "felt"),
         ],
     );
 
@@ -116,34 +85,13 @@ pub fn build_modify_order_message(chain_id: Felt, order: &Order) -> TypedData {
     types.insert(
         "ModifyOrder".to_string(),
         vec![
-            TypeMember {
-                name: "timestamp".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "market".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "side".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "orderType".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "size".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "price".to_string(),
-                type_name: "felt".to_string(),
-            },
-            TypeMember {
-                name: "id".to_string(),
-                type_name: "felt".to_string(),
-            },
+            TypeMember::new("timestamp", "felt"),
+            TypeMember::new("market", "felt"),
+            TypeMember::new("side", "felt"),
+            TypeMember::new("orderType", "felt"),
+            TypeMember::new("size", "felt"),
+            TypeMember::new("price", "felt"),
+            TypeMember::new("id", "felt"),
         ],
     );
 
@@ -161,28 +109,30 @@ pub fn build_modify_order_message(chain_id: Felt, order: &Order) -> TypedData {
         "orderType".to_string(),
         Value::String(order.order_type.to_string()),
     );
-    message.insert("size".to_string(), Value::String(order.chain_size()));
-    message.insert("price".to_string(), Value::String(order.chain_price()));
+    message.insert("size".to_string(), Value::String(order.chain_size()?));
+    message.insert("price".to_string(), Value::String(order.chain_price()?));
     message.insert(
         "id".to_string(),
         Value::String(order.id.clone().unwrap_or_default()),
     );
 
-    TypedData {
+    Ok(TypedData {
         domain: Domain {
             name: "Paradex".to_string(),
             chain_id: format!("{chain_id:#x}"),
             version: "1".to_string(),
+            revision: None,
         },
         primary_type: "ModifyOrder".to_string(),
         types,
         message,
-    }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{ParadexError, QuantumError};
     use crate::types::{OrderSide, OrderType};
 
     #[test]
@@ -206,9 +156,48 @@ mod tests {
         };
 
         let chain_id = Felt::from_hex("0x1").unwrap();
-        let typed_data = build_order_message(chain_id, &order);
+        let typed_data = build_order_message(chain_id, &order).unwrap();
 
         assert_eq!(typed_data.primary_type, "Order");
         assert_eq!(typed_data.domain.name, "Paradex");
     }
+
+    #[test]
+    fn test_order_message_uses_quantums() {
+        let order = Order::builder()
+            .market("BTC-USD-PERP")
+            .side(OrderSide::Buy)
+            .order_type(OrderType::Limit)
+            .size("0.5")
+            .price("42000.25")
+            .build()
+            .unwrap();
+
+        let chain_id = Felt::from_hex("0x1").unwrap();
+        let typed_data = build_order_message(chain_id, &order).unwrap();
+
+        assert_eq!(typed_data.message["size"], "50000000");
+        assert_eq!(typed_data.message["price"], "4200025000000");
+    }
+
+    #[test]
+    fn test_order_message_rejects_precision_loss() {
+        let order = Order::builder()
+            .market("BTC-USD-PERP")
+            .side(OrderSide::Buy)
+            .order_type(OrderType::Market)
+            .size("0.000000001")
+            .build()
+            .unwrap();
+
+        let chain_id = Felt::from_hex("0x1").unwrap();
+        let result = build_order_message(chain_id, &order);
+
+        assert!(matches!(
+            result,
+            Err(ParadexError::QuantumError(
+                QuantumError::PrecisionLoss { .. }
+            ))
+        ));
+    }
 }
@@ -1,9 +1,14 @@
 use crate::error::{ParadexError, Result};
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use starknet_core::utils::starknet_keccak;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use starknet_core::utils::{get_selector_from_name, starknet_keccak};
+use starknet_crypto::{pedersen_hash, poseidon_hash, poseidon_hash_many};
 use starknet_types_core::felt::Felt;
 use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Prefix every SNIP-12 message hash starts with
+const MESSAGE_PREFIX: &str = "StarkNet Message";
 
 /// EIP-712 style typed data for Starknet
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +26,9 @@ pub struct Domain {
     #[serde(rename = "chainId")]
     pub chain_id: String,
     pub version: String,
+    /// SNIP-12 revision, absent for revision 0 domains
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<Revision>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,145 +36,556 @@ pub struct TypeMember {
     pub name: String,
     #[serde(rename = "type")]
     pub type_name: String,
+    /// Referenced type for `enum` and `merkletree` members
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<String>,
 }
 
-impl TypedData {
-    /// Compute the message hash for signing
-    pub fn message_hash(&self) -> Result<Felt> {
-        // Encode domain
-        let _domain_hash = self.encode_type("StarkNetDomain")?;
+impl TypeMember {
+    /// Create a member of the given type
+    pub fn new(name: impl Into<String>, type_name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            type_name: type_name.into(),
+            contains: None,
+        }
+    }
 
-        // Encode message
-        let message_hash = self.encode_message(&self.primary_type)?;
+    /// Create an `enum` or `merkletree` member referencing `contains`
+    pub fn with_contains(
+        name: impl Into<String>,
+        type_name: impl Into<String>,
+        contains: impl Into<String>,
+    ) -> Self {
+        Self {
+            contains: Some(contains.into()),
+            ..Self::new(name, type_name)
+        }
+    }
+}
 
-        // Compute final hash: hash("StarkNet Message", domain_hash, account_address, message_hash)
-        let _prefix = starknet_keccak(b"StarkNet Message");
+/// SNIP-12 revision
+///
+/// Revision 0 is the legacy Pedersen-based encoding used by the Paradex
+/// messages. Revision 1 hashes with Poseidon, escapes type names and adds the
+/// `shortstring`, `u128`, `i128`, `ContractAddress`, `ClassHash`, `timestamp`,
+/// `bool`, `enum` and `string` types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Revision {
+    #[default]
+    V0,
+    V1,
+}
 
-        // For now, we'll return the message hash
-        // Full implementation would combine with domain and account
-        Ok(message_hash)
+impl Revision {
+    /// Name of the domain type for this revision
+    pub fn domain_type(&self) -> &'static str {
+        match self {
+            Revision::V0 => "StarkNetDomain",
+            Revision::V1 => "StarknetDomain",
+        }
     }
 
-    fn encode_type(&self, type_name: &str) -> Result<Felt> {
-        let type_def = self
-            .types
-            .get(type_name)
-            .ok_or_else(|| ParadexError::SigningError(format!("Type not found: {type_name}")))?;
+    /// Hash a list of encoded elements
+    fn hash_elements(&self, elements: &[Felt]) -> Felt {
+        match self {
+            Revision::V0 => compute_hash_on_elements(elements),
+            Revision::V1 => poseidon_hash_many(elements),
+        }
+    }
 
-        let mut encoding = type_name.to_string();
-        encoding.push('(');
+    /// Hash two merkle tree nodes, smallest first
+    fn hash_pair(&self, a: Felt, b: Felt) -> Felt {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        match self {
+            Revision::V0 => pedersen_hash(&low, &high),
+            Revision::V1 => poseidon_hash(low, high),
+        }
+    }
 
-        for (i, member) in type_def.iter().enumerate() {
-            if i > 0 {
-                encoding.push(',');
-            }
-            encoding.push_str(&format!("{} {}", member.type_name, member.name));
+    /// Quote a type or member name (revision 1 only)
+    fn escape(&self, name: &str) -> String {
+        match self {
+            Revision::V0 => name.to_string(),
+            Revision::V1 => format!("\"{name}\""),
+        }
+    }
+}
+
+impl Serialize for Revision {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Revision::V0 => serializer.serialize_str("0"),
+            Revision::V1 => serializer.serialize_str("1"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Revision {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::Number(n) if n.as_u64() == Some(0) => Ok(Revision::V0),
+            Value::Number(n) if n.as_u64() == Some(1) => Ok(Revision::V1),
+            Value::String(s) if s == "0" => Ok(Revision::V0),
+            Value::String(s) if s == "1" => Ok(Revision::V1),
+            other => Err(serde::de::Error::custom(format!(
+                "unsupported revision: {other}"
+            ))),
         }
-        encoding.push(')');
+    }
+}
 
-        let hash = starknet_keccak(encoding.as_bytes());
-        Ok(hash)
+/// Types implicitly available to revision 1 messages
+fn preset_types() -> &'static HashMap<String, Vec<TypeMember>> {
+    static PRESET_TYPES: OnceLock<HashMap<String, Vec<TypeMember>>> = OnceLock::new();
+    PRESET_TYPES.get_or_init(|| {
+        HashMap::from([
+            (
+                "u256".to_string(),
+                vec![
+                    TypeMember::new("low", "u128"),
+                    TypeMember::new("high", "u128"),
+                ],
+            ),
+            (
+                "TokenAmount".to_string(),
+                vec![
+                    TypeMember::new("token_address", "ContractAddress"),
+                    TypeMember::new("amount", "u256"),
+                ],
+            ),
+            (
+                "NftId".to_string(),
+                vec![
+                    TypeMember::new("collection_address", "ContractAddress"),
+                    TypeMember::new("token_id", "u256"),
+                ],
+            ),
+        ])
+    })
+}
+
+impl TypedData {
+    /// SNIP-12 revision declared by the domain
+    pub fn revision(&self) -> Revision {
+        self.domain.revision.unwrap_or_default()
     }
 
-    fn encode_message(&self, type_name: &str) -> Result<Felt> {
-        let type_hash = self.encode_type(type_name)?;
+    /// Compute the message hash signed by `account_address`
+    ///
+    /// This is `h("StarkNet Message", domain_hash, account_address, struct_hash)`
+    /// where `h` is the Pedersen hash chain over the elements and their count
+    /// for revision 0, and the Poseidon hash of the elements for revision 1.
+    pub fn message_hash(&self, account_address: Felt) -> Result<Felt> {
+        let prefix = encode_short_string(MESSAGE_PREFIX)?;
+        let domain_hash = self.domain_hash()?;
+        let message = Value::Object(Map::from_iter(self.message.clone()));
+        let struct_hash = self.struct_hash(&self.primary_type, &message)?;
 
-        let type_def = self
-            .types
-            .get(type_name)
-            .ok_or_else(|| ParadexError::SigningError(format!("Type not found: {type_name}")))?;
+        Ok(self
+            .revision()
+            .hash_elements(&[prefix, domain_hash, account_address, struct_hash]))
+    }
 
-        let mut values = vec![type_hash];
+    /// Compute the struct hash of the domain
+    pub fn domain_hash(&self) -> Result<Felt> {
+        let domain = serde_json::to_value(&self.domain)?;
+        self.struct_hash(self.revision().domain_type(), &domain)
+    }
+
+    /// Compute the struct hash of `data` encoded as `type_name`
+    pub fn struct_hash(&self, type_name: &str, data: &Value) -> Result<Felt> {
+        let revision = self.revision();
+        let type_def = self.type_def(type_name)?;
 
+        let mut values = vec![self.type_hash(type_name)?];
         for member in type_def {
-            let value = self.message.get(&member.name).ok_or_else(|| {
+            let value = data.get(&member.name).ok_or_else(|| {
                 ParadexError::SigningError(format!("Missing field: {}", member.name))
             })?;
+            values.push(self.encode_value(&member.type_name, member.contains.as_deref(), value)?);
+        }
+
+        Ok(revision.hash_elements(&values))
+    }
 
-            let encoded_value = self.encode_value(&member.type_name, value)?;
-            values.push(encoded_value);
+    /// Compute the type hash, the Starknet keccak of the encoded type
+    pub fn type_hash(&self, type_name: &str) -> Result<Felt> {
+        Ok(starknet_keccak(self.encode_type(type_name)?.as_bytes()))
+    }
+
+    /// Encode a type as `Name(member:type,...)` followed by its sorted dependencies
+    ///
+    /// Revision 1 quotes every name, e.g. `"Name"("member":"type")`.
+    pub fn encode_type(&self, type_name: &str) -> Result<String> {
+        let revision = self.revision();
+        let mut dependencies = Vec::new();
+        self.collect_dependencies(type_name, None, &mut dependencies);
+        if dependencies.is_empty() {
+            return Err(ParadexError::SigningError(format!(
+                "Type not found: {type_name}"
+            )));
         }
+        dependencies[1..].sort();
 
-        // Hash all values together
-        let mut hash_input = Vec::new();
-        for value in values {
-            hash_input.extend_from_slice(&value.to_bytes_be());
+        let mut encoding = String::new();
+        for dependency in dependencies {
+            let members: Vec<String> = self
+                .type_def(dependency)?
+                .iter()
+                .map(|member| {
+                    format!(
+                        "{}:{}",
+                        revision.escape(&member.name),
+                        Self::encode_member_type(member, revision)
+                    )
+                })
+                .collect();
+            encoding.push_str(&format!(
+                "{}({})",
+                revision.escape(dependency),
+                members.join(",")
+            ));
         }
 
-        let hash = starknet_keccak(&hash_input);
-        Ok(hash)
+        Ok(encoding)
     }
 
-    fn encode_value(&self, type_name: &str, value: &Value) -> Result<Felt> {
-        match type_name {
-            "felt" => {
-                if let Value::String(s) = value {
-                    // Try to parse as hex or decimal
-                    if s.starts_with("0x") {
-                        Felt::from_hex(s)
-                            .map_err(|e| ParadexError::SigningError(format!("Invalid felt: {e}")))
-                    } else {
-                        // Try to parse as decimal first
-                        let s_clean = s.trim_end_matches(".0").trim_end_matches('.');
-                        if let Ok(felt) = Felt::from_dec_str(s_clean) {
-                            Ok(felt)
+    fn encode_member_type(member: &TypeMember, revision: Revision) -> String {
+        let target = match (revision, member.contains.as_deref()) {
+            (Revision::V1, Some(contains)) if member.type_name == "enum" => contains,
+            _ => member.type_name.as_str(),
+        };
+
+        match tuple_members(target) {
+            Some(elements) if revision == Revision::V1 => {
+                let elements: Vec<String> = elements
+                    .map(|element| {
+                        if element.is_empty() {
+                            String::new()
                         } else {
-                            // If it's not a number, encode as short string (ASCII to felt)
-                            Self::encode_short_string(s)
+                            revision.escape(element)
                         }
-                    }
-                } else {
-                    Err(ParadexError::SigningError(
-                        "Expected string for felt".to_string(),
-                    ))
+                    })
+                    .collect();
+                format!("({})", elements.join(","))
+            }
+            _ => revision.escape(target),
+        }
+    }
+
+    /// Look up a type, including the revision 1 preset types
+    fn lookup_type(&self, type_name: &str) -> Option<(&str, &Vec<TypeMember>)> {
+        self.types
+            .get_key_value(type_name)
+            .or_else(|| match self.revision() {
+                Revision::V0 => None,
+                Revision::V1 => preset_types().get_key_value(type_name),
+            })
+            .map(|(name, members)| (name.as_str(), members))
+    }
+
+    fn type_def(&self, type_name: &str) -> Result<&Vec<TypeMember>> {
+        self.lookup_type(type_name)
+            .map(|(_, members)| members)
+            .ok_or_else(|| ParadexError::SigningError(format!("Type not found: {type_name}")))
+    }
+
+    fn collect_dependencies<'a>(
+        &'a self,
+        type_name: &str,
+        contains: Option<&str>,
+        dependencies: &mut Vec<&'a str>,
+    ) {
+        let revision = self.revision();
+
+        if let Some(element_type) = type_name.strip_suffix('*') {
+            return self.collect_dependencies(element_type, None, dependencies);
+        }
+        if revision == Revision::V1 {
+            if type_name == "enum" {
+                if let Some(contains) = contains {
+                    self.collect_dependencies(contains, None, dependencies);
                 }
+                return;
             }
-            _ => {
-                // For other types, try to encode as felt
-                if let Value::String(s) = value {
-                    if s.starts_with("0x") {
-                        Felt::from_hex(s).map_err(|e| {
-                            ParadexError::SigningError(format!("Invalid value: {e}"))
-                        })
-                    } else {
-                        // Try decimal, fall back to short string
-                        let s_clean = s.trim_end_matches(".0").trim_end_matches('.');
-                        if let Ok(felt) = Felt::from_dec_str(s_clean) {
-                            Ok(felt)
-                        } else {
-                            Self::encode_short_string(s)
-                        }
-                    }
-                } else {
-                    Err(ParadexError::SigningError(
-                        "Expected string value".to_string(),
-                    ))
+            if let Some(elements) = tuple_members(type_name) {
+                for element in elements {
+                    self.collect_dependencies(element, None, dependencies);
                 }
+                return;
             }
         }
+
+        let Some((name, members)) = self.lookup_type(type_name) else {
+            return;
+        };
+        if dependencies.contains(&name) {
+            return;
+        }
+
+        dependencies.push(name);
+        for member in members {
+            self.collect_dependencies(&member.type_name, member.contains.as_deref(), dependencies);
+        }
     }
 
-    fn encode_short_string(s: &str) -> Result<Felt> {
-        // Encode ASCII string as Felt (Cairo short string)
-        // Max 31 characters
-        if s.len() > 31 {
-            return Err(ParadexError::SigningError(format!(
-                "String too long for short string encoding: {s}"
-            )));
+    fn encode_value(&self, type_name: &str, contains: Option<&str>, value: &Value) -> Result<Felt> {
+        let revision = self.revision();
+
+        if self.lookup_type(type_name).is_some() {
+            return self.struct_hash(type_name, value);
         }
 
-        let mut bytes = [0u8; 32];
-        let s_bytes = s.as_bytes();
-        bytes[32 - s_bytes.len()..].copy_from_slice(s_bytes);
+        if let Some(element_type) = type_name.strip_suffix('*') {
+            let elements = value.as_array().ok_or_else(|| {
+                ParadexError::SigningError(format!("Expected array for {type_name}"))
+            })?;
+            let encoded = elements
+                .iter()
+                .map(|element| self.encode_value(element_type, None, element))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(revision.hash_elements(&encoded));
+        }
 
-        Ok(Felt::from_bytes_be(&bytes))
+        match (revision, type_name) {
+            (_, "merkletree") => self.encode_merkle_tree(contains, value),
+            (_, "selector") => match value {
+                Value::String(s) if !s.starts_with("0x") => get_selector_from_name(s)
+                    .map_err(|e| ParadexError::SigningError(format!("Invalid selector: {e}"))),
+                _ => encode_felt(value),
+            },
+            (Revision::V0, _) => encode_felt(value),
+            (Revision::V1, "felt" | "shortstring" | "ContractAddress" | "ClassHash") => {
+                encode_felt(value)
+            }
+            (Revision::V1, "u128" | "timestamp") => {
+                let felt = encode_felt(value)?;
+                if felt.to_bytes_be()[..16].iter().any(|byte| *byte != 0) {
+                    return Err(ParadexError::SigningError(format!(
+                        "{value} out of range for {type_name}"
+                    )));
+                }
+                Ok(felt)
+            }
+            (Revision::V1, "i128") => encode_i128(value),
+            (Revision::V1, "bool") => {
+                let felt = encode_felt(value)?;
+                if felt > Felt::ONE {
+                    return Err(ParadexError::SigningError(format!(
+                        "{value} out of range for bool"
+                    )));
+                }
+                Ok(felt)
+            }
+            (Revision::V1, "string") => match value {
+                Value::String(s) => Ok(encode_byte_array(s)),
+                _ => Err(ParadexError::SigningError(format!(
+                    "Expected string, got {value}"
+                ))),
+            },
+            (Revision::V1, "enum") => self.encode_enum(contains, value),
+            (Revision::V1, _) => Err(ParadexError::SigningError(format!(
+                "Unsupported type: {type_name}"
+            ))),
+        }
     }
+
+    /// Encode an enum value `{ "Variant": [params...] }` as
+    /// `h(variant_index, ...encoded_params)`
+    fn encode_enum(&self, contains: Option<&str>, value: &Value) -> Result<Felt> {
+        let enum_name = contains
+            .ok_or_else(|| ParadexError::SigningError("Enum member without contains".into()))?;
+        let variants = self.type_def(enum_name)?;
+
+        let (variant_name, params) = value
+            .as_object()
+            .and_then(|object| object.iter().next())
+            .ok_or_else(|| {
+                ParadexError::SigningError(format!("Expected enum variant, got {value}"))
+            })?;
+        let index = variants
+            .iter()
+            .position(|variant| variant.name == *variant_name)
+            .ok_or_else(|| {
+                ParadexError::SigningError(format!("Unknown variant {variant_name} of {enum_name}"))
+            })?;
+        let param_types = tuple_members(&variants[index].type_name).ok_or_else(|| {
+            ParadexError::SigningError(format!("Invalid variant type of {variant_name}"))
+        })?;
+
+        let mut elements = vec![Felt::from(index as u64)];
+        for (i, param_type) in param_types.enumerate() {
+            // starknet.js encodes the empty slot of a unit variant `()` as 0
+            if param_type.is_empty() {
+                elements.push(Felt::ZERO);
+                continue;
+            }
+            let param = params.get(i).ok_or_else(|| {
+                ParadexError::SigningError(format!("Missing parameter {i} of {variant_name}"))
+            })?;
+            elements.push(self.encode_value(param_type, None, param)?);
+        }
+
+        Ok(self.revision().hash_elements(&elements))
+    }
+
+    /// Encode a list of leaves as the root of their merkle tree
+    fn encode_merkle_tree(&self, contains: Option<&str>, value: &Value) -> Result<Felt> {
+        let revision = self.revision();
+        let leaf_type = contains
+            .ok_or_else(|| ParadexError::SigningError("Merkle tree without contains".into()))?;
+        let leaves = value.as_array().ok_or_else(|| {
+            ParadexError::SigningError(format!("Expected array of leaves, got {value}"))
+        })?;
+
+        let mut layer = leaves
+            .iter()
+            .map(|leaf| self.encode_value(leaf_type, None, leaf))
+            .collect::<Result<Vec<_>>>()?;
+        if layer.is_empty() {
+            return Err(ParadexError::SigningError(
+                "Merkle tree must have at least one leaf".to_string(),
+            ));
+        }
+
+        while layer.len() > 1 {
+            layer = layer
+                .chunks(2)
+                .map(|pair| revision.hash_pair(pair[0], pair.get(1).copied().unwrap_or_default()))
+                .collect();
+        }
+
+        Ok(layer[0])
+    }
+}
+
+/// Split a tuple type `(a,b)` into its element types
+fn tuple_members(type_name: &str) -> Option<std::str::Split<'_, char>> {
+    type_name
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+        .map(|inner| inner.split(','))
+}
+
+/// Encode a felt from a hex string, whole decimal string, number, bool
+/// or, failing those, an ASCII short string
+fn encode_felt(value: &Value) -> Result<Felt> {
+    match value {
+        Value::String(s) => {
+            if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Felt::from_hex(&format!("0x{hex}"))
+                    .map_err(|e| ParadexError::SigningError(format!("Invalid felt: {e}")))
+            } else if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+                Felt::from_dec_str(s)
+                    .map_err(|e| ParadexError::SigningError(format!("Invalid felt: {e}")))
+            } else {
+                encode_short_string(s)
+            }
+        }
+        Value::Number(n) => n
+            .as_u64()
+            .map(Felt::from)
+            .ok_or_else(|| ParadexError::SigningError(format!("Invalid felt number: {n}"))),
+        Value::Bool(b) => Ok(if *b { Felt::ONE } else { Felt::ZERO }),
+        _ => Err(ParadexError::SigningError(format!(
+            "Cannot encode {value} as felt"
+        ))),
+    }
+}
+
+/// Encode a signed 128-bit integer, negative values wrapping around the field
+fn encode_i128(value: &Value) -> Result<Felt> {
+    let parsed = match value {
+        Value::Number(n) => n.as_i64().map(i128::from),
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => i128::from_str_radix(hex, 16).ok(),
+            None => s.parse::<i128>().ok(),
+        },
+        _ => None,
+    };
+
+    parsed
+        .map(Felt::from)
+        .ok_or_else(|| ParadexError::SigningError(format!("{value} out of range for i128")))
+}
+
+/// Encode a string as the Poseidon hash of its Cairo `ByteArray` serialization
+fn encode_byte_array(s: &str) -> Felt {
+    let chunks: Vec<&[u8]> = s.as_bytes().chunks(31).collect();
+    let (full, pending) = match chunks.last() {
+        Some(last) if last.len() < 31 => (&chunks[..chunks.len() - 1], *last),
+        _ => (&chunks[..], &[][..]),
+    };
+
+    let mut elements = vec![Felt::from(full.len() as u64)];
+    elements.extend(full.iter().map(|chunk| felt_from_bytes(chunk)));
+    elements.push(felt_from_bytes(pending));
+    elements.push(Felt::from(pending.len() as u64));
+
+    poseidon_hash_many(&elements)
+}
+
+fn encode_short_string(s: &str) -> Result<Felt> {
+    // Encode ASCII string as Felt (Cairo short string)
+    // Max 31 characters
+    if s.len() > 31 || !s.is_ascii() {
+        return Err(ParadexError::SigningError(format!(
+            "Invalid short string: {s}"
+        )));
+    }
+
+    Ok(felt_from_bytes(s.as_bytes()))
+}
+
+/// Interpret up to 31 bytes as a big-endian felt
+fn felt_from_bytes(bytes: &[u8]) -> Felt {
+    let mut buffer = [0u8; 32];
+    buffer[32 - bytes.len()..].copy_from_slice(bytes);
+    Felt::from_bytes_be(&buffer)
+}
+
+/// Pedersen hash chain over `elements` terminated by their count
+fn compute_hash_on_elements(elements: &[Felt]) -> Felt {
+    let hash = elements
+        .iter()
+        .fold(Felt::ZERO, |acc, element| pedersen_hash(&acc, element));
+    pedersen_hash(&hash, &Felt::from(elements.len() as u64))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn mail_typed_data() -> TypedData {
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "StarkNetDomain": [
+                    { "name": "name", "type": "felt" },
+                    { "name": "version", "type": "felt" },
+                    { "name": "chainId", "type": "felt" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "felt" },
+                    { "name": "wallet", "type": "felt" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "felt" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": { "name": "StarkNet Mail", "version": "1", "chainId": "1" },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap()
+    }
+
     #[test]
     fn test_typed_data_structure() {
         let typed_data = TypedData {
@@ -174,6 +593,7 @@ mod tests {
                 name: "Paradex".to_string(),
                 chain_id: "0x1".to_string(),
                 version: "1".to_string(),
+                revision: None,
             },
             primary_type: "Order".to_string(),
             types: HashMap::new(),
@@ -183,4 +603,204 @@ mod tests {
         assert_eq!(typed_data.domain.name, "Paradex");
         assert_eq!(typed_data.primary_type, "Order");
     }
+
+    #[test]
+    fn test_encode_type_with_dependencies() {
+        let typed_data = mail_typed_data();
+        assert_eq!(
+            typed_data.encode_type("Mail").unwrap(),
+            "Mail(from:Person,to:Person,contents:felt)Person(name:felt,wallet:felt)"
+        );
+        assert_eq!(
+            typed_data.type_hash("StarkNetDomain").unwrap(),
+            Felt::from_hex("0x1bfc207425a47a5dfa1a50a4f5241203f50624ca5fdf5e18755765416b8e288")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_message_hash_matches_reference() {
+        let typed_data = mail_typed_data();
+        let account = Felt::from_hex("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826").unwrap();
+        assert_eq!(
+            typed_data.message_hash(account).unwrap(),
+            Felt::from_hex("0x6fcff244f63e38b9d88b9e3378d44757710d1b244282b435cb472053c8d78d0")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_felt_array_hash() {
+        let mut typed_data = mail_typed_data();
+        typed_data
+            .types
+            .insert("Batch".to_string(), vec![TypeMember::new("items", "felt*")]);
+
+        let data = serde_json::json!({ "items": ["0x1", "0x2", "0x3"] });
+        let items_hash = compute_hash_on_elements(&[Felt::ONE, Felt::TWO, Felt::from(3u64)]);
+        let expected =
+            compute_hash_on_elements(&[typed_data.type_hash("Batch").unwrap(), items_hash]);
+        assert_eq!(typed_data.struct_hash("Batch", &data).unwrap(), expected);
+    }
+
+    fn revision_1_typed_data() -> TypedData {
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "StarknetDomain": [
+                    { "name": "name", "type": "shortstring" },
+                    { "name": "version", "type": "shortstring" },
+                    { "name": "chainId", "type": "shortstring" },
+                    { "name": "revision", "type": "shortstring" }
+                ],
+                "Transfer": [
+                    { "name": "recipient", "type": "ContractAddress" },
+                    { "name": "amount", "type": "u128" },
+                    { "name": "delta", "type": "i128" },
+                    { "name": "enabled", "type": "bool" }
+                ]
+            },
+            "primaryType": "Transfer",
+            "domain": { "name": "Paradex", "version": "1", "chainId": "SN_SEPOLIA", "revision": "1" },
+            "message": {
+                "recipient": "0x1234",
+                "amount": "1000",
+                "delta": -5,
+                "enabled": true
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_revision_deserialization() {
+        let typed_data = revision_1_typed_data();
+        assert_eq!(typed_data.revision(), Revision::V1);
+        assert_eq!(mail_typed_data().revision(), Revision::V0);
+
+        let domain: Domain = serde_json::from_value(serde_json::json!({
+            "name": "Paradex", "version": "1", "chainId": "1", "revision": 1
+        }))
+        .unwrap();
+        assert_eq!(domain.revision, Some(Revision::V1));
+    }
+
+    #[test]
+    fn test_revision_1_encode_type() {
+        let typed_data = revision_1_typed_data();
+        assert_eq!(
+            typed_data.encode_type("StarknetDomain").unwrap(),
+            r#""StarknetDomain"("name":"shortstring","version":"shortstring","chainId":"shortstring","revision":"shortstring")"#
+        );
+        assert_eq!(
+            typed_data.type_hash("StarknetDomain").unwrap(),
+            Felt::from_hex("0x1ff2f602e42168014d405a94f75e8a93d640751d71d16311266e140d8b0a210")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_revision_1_message_hash() {
+        let typed_data = revision_1_typed_data();
+        let account = Felt::from_hex("0xabc").unwrap();
+
+        let domain_hash = poseidon_hash_many(&[
+            typed_data.type_hash("StarknetDomain").unwrap(),
+            encode_short_string("Paradex").unwrap(),
+            Felt::ONE,
+            encode_short_string("SN_SEPOLIA").unwrap(),
+            Felt::ONE,
+        ]);
+        let struct_hash = poseidon_hash_many(&[
+            typed_data.type_hash("Transfer").unwrap(),
+            Felt::from_hex("0x1234").unwrap(),
+            Felt::from(1000u64),
+            Felt::ZERO - Felt::from(5u64),
+            Felt::ONE,
+        ]);
+        let expected = poseidon_hash_many(&[
+            encode_short_string("StarkNet Message").unwrap(),
+            domain_hash,
+            account,
+            struct_hash,
+        ]);
+
+        assert_eq!(typed_data.domain_hash().unwrap(), domain_hash);
+        assert_eq!(typed_data.message_hash(account).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_revision_1_range_checks() {
+        let mut typed_data = revision_1_typed_data();
+        typed_data.message.insert(
+            "amount".to_string(),
+            Value::String("0x100000000000000000000000000000000".to_string()),
+        );
+        assert!(typed_data.message_hash(Felt::ONE).is_err());
+
+        let mut typed_data = revision_1_typed_data();
+        typed_data
+            .message
+            .insert("enabled".to_string(), Value::from(2));
+        assert!(typed_data.message_hash(Felt::ONE).is_err());
+
+        let mut typed_data = revision_1_typed_data();
+        typed_data.types.insert(
+            "Transfer".to_string(),
+            vec![TypeMember::new("recipient", "address")],
+        );
+        assert!(typed_data.message_hash(Felt::ONE).is_err());
+    }
+
+    #[test]
+    fn test_revision_1_enum() {
+        let mut typed_data = revision_1_typed_data();
+        typed_data.types.insert(
+            "Action".to_string(),
+            vec![
+                TypeMember::new("Cancel", "()"),
+                TypeMember::new("Fill", "(u128,bool)"),
+            ],
+        );
+        typed_data.types.insert(
+            "Request".to_string(),
+            vec![TypeMember::with_contains("action", "enum", "Action")],
+        );
+
+        assert_eq!(
+            typed_data.encode_type("Request").unwrap(),
+            r#""Request"("action":"Action")"Action"("Cancel":(),"Fill":("u128","bool"))"#
+        );
+
+        let data = serde_json::json!({ "action": { "Fill": ["7", true] } });
+        let action_hash = poseidon_hash_many(&[Felt::ONE, Felt::from(7u64), Felt::ONE]);
+        let expected = poseidon_hash_many(&[typed_data.type_hash("Request").unwrap(), action_hash]);
+        assert_eq!(typed_data.struct_hash("Request", &data).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_merkle_tree_root() {
+        let mut typed_data = revision_1_typed_data();
+        typed_data.types.insert(
+            "Session".to_string(),
+            vec![TypeMember::with_contains("root", "merkletree", "felt")],
+        );
+
+        let data = serde_json::json!({ "root": ["0x1", "0x2", "0x3"] });
+        let left = poseidon_hash(Felt::ONE, Felt::TWO);
+        let right = poseidon_hash(Felt::ZERO, Felt::from(3u64));
+        let root = poseidon_hash(left.min(right), left.max(right));
+        let expected = poseidon_hash_many(&[typed_data.type_hash("Session").unwrap(), root]);
+        assert_eq!(typed_data.struct_hash("Session", &data).unwrap(), expected);
+
+        let empty = serde_json::json!({ "root": [] });
+        assert!(typed_data.struct_hash("Session", &empty).is_err());
+    }
+
+    #[test]
+    fn test_byte_array_encoding() {
+        let text = "a".repeat(33);
+        let chunk = felt_from_bytes(&[b'a'; 31]);
+        let expected = poseidon_hash_many(&[Felt::ONE, chunk, felt_from_bytes(b"aa"), Felt::TWO]);
+        assert_eq!(encode_byte_array(&text), expected);
+    }
 }
@@ -0,0 +1,150 @@
+//! Secret wrappers
+//!
+//! Private keys and tokens are held in types that zeroize their memory on drop
+//! and never print their contents through `Debug` or `Display`. Call
+//! `expose_secret` at the point the raw value is actually needed.
+
+use crate::error::{ParadexError, Result};
+use serde::{Deserialize, Deserializer};
+use starknet_types_core::felt::Felt;
+use zeroize::Zeroize;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// String secret such as an Ethereum private key or a JWT
+#[derive(Clone, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap a secret string
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// Access the raw secret
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl From<&String> for SecretString {
+    fn from(secret: &String) -> Self {
+        Self(secret.clone())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl std::fmt::Display for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Stark private key
+#[derive(Clone)]
+pub struct SecretKey([u8; 32]);
+
+impl SecretKey {
+    /// Wrap a Stark private key
+    pub fn from_felt(key: Felt) -> Self {
+        Self(key.to_bytes_be())
+    }
+
+    /// Parse a hex encoded Stark private key
+    pub fn from_hex(key: &str) -> Result<Self> {
+        // The parse error never includes the input, so the key cannot leak
+        let key = Felt::from_hex(key)
+            .map_err(|_| ParadexError::ConfigError("Invalid L2 key".to_string()))?;
+        Ok(Self::from_felt(key))
+    }
+
+    /// Access the raw key
+    pub fn expose_secret(&self) -> Felt {
+        Felt::from_bytes_be(&self.0)
+    }
+
+    /// Stark public key for this private key
+    pub fn public_key(&self) -> Felt {
+        starknet_crypto::get_public_key(&self.expose_secret())
+    }
+}
+
+impl From<Felt> for SecretKey {
+    fn from(key: Felt) -> Self {
+        Self::from_felt(key)
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl std::fmt::Display for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_string_is_redacted() {
+        let secret = SecretString::new("eyJhbGciOiJIUzI1NiJ9.secret");
+        assert_eq!(format!("{secret:?}"), REDACTED);
+        assert_eq!(format!("{secret}"), REDACTED);
+        assert_eq!(secret.expose_secret(), "eyJhbGciOiJIUzI1NiJ9.secret");
+
+        let parsed: SecretString = serde_json::from_str(r#""token""#).unwrap();
+        assert_eq!(parsed.expose_secret(), "token");
+    }
+
+    #[test]
+    fn test_secret_key_is_redacted() {
+        let key = SecretKey::from_hex("0xdeadbeef").unwrap();
+        assert_eq!(format!("{key:?}"), REDACTED);
+        assert_eq!(format!("{key}"), REDACTED);
+        assert_eq!(key.expose_secret(), Felt::from_hex("0xdeadbeef").unwrap());
+
+        let error = SecretKey::from_hex("0xnotakey").unwrap_err();
+        assert!(!error.to_string().contains("notakey"));
+    }
+}
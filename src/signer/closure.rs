@@ -0,0 +1,78 @@
+use crate::{error::Result, signer::Signer};
+use async_trait::async_trait;
+use starknet_types_core::felt::Felt;
+use std::future::Future;
+
+/// Signer delegating to a user supplied async closure
+///
+/// # Example
+///
+/// ```rust
+/// use paradex_rs::signer::{FnSigner, InMemorySigner, Signer};
+/// use starknet_types_core::felt::Felt;
+///
+/// let key = InMemorySigner::new(Felt::from(42u64));
+/// let signer = FnSigner::new(key.public_key(), move |hash| {
+///     let key = key.clone();
+///     async move { key.sign_hash(hash).await }
+/// });
+/// ```
+pub struct FnSigner<F> {
+    public_key: Felt,
+    sign: F,
+}
+
+impl<F, Fut> FnSigner<F>
+where
+    F: Fn(Felt) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<(Felt, Felt)>> + Send,
+{
+    /// Create a signer for `public_key` that signs with `sign`
+    pub fn new(public_key: Felt, sign: F) -> Self {
+        Self { public_key, sign }
+    }
+}
+
+#[async_trait]
+impl<F, Fut> Signer for FnSigner<F>
+where
+    F: Fn(Felt) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<(Felt, Felt)>> + Send,
+{
+    fn public_key(&self) -> Felt {
+        self.public_key
+    }
+
+    async fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)> {
+        (self.sign)(hash).await
+    }
+}
+
+impl<F> std::fmt::Debug for FnSigner<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnSigner")
+            .field("public_key", &format!("{:#x}", self.public_key))
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParadexError;
+
+    #[tokio::test]
+    async fn test_closure_is_called() {
+        let signer = FnSigner::new(Felt::ONE, |hash: Felt| async move { Ok((hash, Felt::TWO)) });
+        assert_eq!(signer.public_key(), Felt::ONE);
+        assert_eq!(
+            signer.sign_hash(Felt::THREE).await.unwrap(),
+            (Felt::THREE, Felt::TWO)
+        );
+
+        let failing = FnSigner::new(Felt::ONE, |_| async {
+            Err(ParadexError::SigningError("declined".to_string()))
+        });
+        assert!(failing.sign_hash(Felt::ONE).await.is_err());
+    }
+}
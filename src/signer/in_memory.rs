@@ -0,0 +1,77 @@
+use crate::{
+    error::{ParadexError, Result},
+    secret::SecretKey,
+    signer::Signer,
+};
+use async_trait::async_trait;
+use starknet_types_core::felt::Felt;
+
+/// Signer holding the Stark private key in process memory
+#[derive(Clone)]
+pub struct InMemorySigner {
+    private_key: SecretKey,
+    public_key: Felt,
+}
+
+impl InMemorySigner {
+    /// Create a signer from a Stark private key
+    pub fn new(private_key: impl Into<SecretKey>) -> Self {
+        let private_key = private_key.into();
+        Self {
+            public_key: private_key.public_key(),
+            private_key,
+        }
+    }
+
+    /// Create a signer from a hex encoded Stark private key
+    pub fn from_hex(private_key: &str) -> Result<Self> {
+        Ok(Self::new(SecretKey::from_hex(private_key)?))
+    }
+
+    /// Sign without going through the async trait
+    ///
+    /// The nonce is derived from the hash and the key with RFC 6979.
+    pub fn sign_hash_sync(&self, hash: Felt) -> Result<(Felt, Felt)> {
+        let private_key = self.private_key.expose_secret();
+        let k = starknet_crypto::rfc6979_generate_k(&hash, &private_key, None);
+        let signature = starknet_crypto::sign(&private_key, &hash, &k)
+            .map_err(|e| ParadexError::SigningError(format!("Signing failed: {e}")))?;
+
+        Ok((signature.r, signature.s))
+    }
+}
+
+#[async_trait]
+impl Signer for InMemorySigner {
+    fn public_key(&self) -> Felt {
+        self.public_key
+    }
+
+    async fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)> {
+        self.sign_hash_sync(hash)
+    }
+}
+
+impl std::fmt::Debug for InMemorySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemorySigner")
+            .field("public_key", &format!("{:#x}", self.public_key))
+            .field("private_key", &self.private_key)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_signature_verifies() {
+        let signer = InMemorySigner::from_hex("0x1234").unwrap();
+        let hash = Felt::from_hex("0xabcdef").unwrap();
+
+        let (r, s) = signer.sign_hash(hash).await.unwrap();
+        assert!(starknet_crypto::verify(&signer.public_key(), &hash, &r, &s).unwrap());
+        assert!(!format!("{signer:?}").contains("1234"));
+    }
+}
@@ -0,0 +1,27 @@
+//! Stark signers
+//!
+//! Accounts never touch key material directly; every signature goes through a
+//! [`Signer`]. The SDK ships an in-memory signer, a remote signer talking to a
+//! signing daemon and a closure-backed signer for custom integrations.
+
+mod closure;
+mod in_memory;
+mod remote;
+
+pub use closure::FnSigner;
+pub use in_memory::InMemorySigner;
+pub use remote::{RemoteSigner, RemoteTransport};
+
+use crate::error::Result;
+use async_trait::async_trait;
+use starknet_types_core::felt::Felt;
+
+/// Signs Starknet message hashes on behalf of an account
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Stark public key matching the signing key
+    fn public_key(&self) -> Felt;
+
+    /// Sign a message hash, returning the `(r, s)` signature
+    async fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)>;
+}
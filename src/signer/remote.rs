@@ -0,0 +1,321 @@
+use crate::{
+    error::{ParadexError, Result},
+    signer::Signer,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Default limit on one round trip to the signing daemon
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How to reach the signing daemon
+#[derive(Debug, Clone)]
+pub enum RemoteTransport {
+    /// POST the request as JSON to this URL
+    Http(String),
+    /// Write the request as a single JSON line to this Unix socket
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Request sent to the signing daemon
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    public_key: String,
+    hash: String,
+}
+
+/// Response from the signing daemon, either a signature or an error
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    r: Option<String>,
+    s: Option<String>,
+    error: Option<String>,
+}
+
+/// Signer delegating to an external signing daemon
+///
+/// The daemon receives `{"public_key": "0x..", "hash": "0x.."}` and answers
+/// with `{"r": "0x..", "s": "0x.."}` or `{"error": ".."}`. Returned signatures
+/// are verified against the public key before being used.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    public_key: Felt,
+    transport: RemoteTransport,
+    timeout: Duration,
+    http: reqwest::Client,
+}
+
+impl RemoteSigner {
+    /// Create a signer for `public_key` reachable over `transport`
+    pub fn new(public_key: Felt, transport: RemoteTransport) -> Self {
+        Self {
+            public_key,
+            transport,
+            timeout: DEFAULT_TIMEOUT,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Fail requests the daemon hasn't answered within `timeout` (10s by default)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Create a signer reachable over HTTP
+    pub fn http(public_key: Felt, url: impl Into<String>) -> Self {
+        Self::new(public_key, RemoteTransport::Http(url.into()))
+    }
+
+    /// Create a signer reachable over a Unix socket
+    #[cfg(unix)]
+    pub fn unix(public_key: Felt, path: impl Into<PathBuf>) -> Self {
+        Self::new(public_key, RemoteTransport::Unix(path.into()))
+    }
+
+    async fn send(&self, request: &SignRequest) -> Result<SignResponse> {
+        match &self.transport {
+            RemoteTransport::Http(url) => {
+                let timed_out = |e: reqwest::Error, what: &str| {
+                    if e.is_timeout() {
+                        remote_error(format!("timed out after {:?}", self.timeout))
+                    } else {
+                        remote_error(format!("{what}: {e}"))
+                    }
+                };
+                let response = self
+                    .http
+                    .post(url)
+                    .timeout(self.timeout)
+                    .json(request)
+                    .send()
+                    .await
+                    .map_err(|e| timed_out(e, "request failed"))?;
+                if let Err(e) = response.error_for_status_ref() {
+                    // The body usually says why, e.g. a locked key
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(remote_error(format!("{e}: {body}")));
+                }
+                response
+                    .json()
+                    .await
+                    .map_err(|e| timed_out(e, "invalid response"))
+            }
+            #[cfg(unix)]
+            RemoteTransport::Unix(path) => {
+                tokio::time::timeout(self.timeout, Self::send_unix(path, request))
+                    .await
+                    .map_err(|_| remote_error(format!("timed out after {:?}", self.timeout)))?
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    async fn send_unix(path: &std::path::Path, request: &SignRequest) -> Result<SignResponse> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let mut stream = tokio::net::UnixStream::connect(path)
+            .await
+            .map_err(|e| remote_error(format!("cannot connect to {path:?}: {e}")))?;
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+        stream
+            .write_all(&line)
+            .await
+            .map_err(|e| remote_error(format!("write failed: {e}")))?;
+
+        let mut response = String::new();
+        BufReader::new(stream)
+            .read_line(&mut response)
+            .await
+            .map_err(|e| remote_error(format!("read failed: {e}")))?;
+        serde_json::from_str(&response).map_err(|e| remote_error(format!("invalid response: {e}")))
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> Felt {
+        self.public_key
+    }
+
+    async fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)> {
+        let request = SignRequest {
+            public_key: format!("{:#x}", self.public_key),
+            hash: format!("{hash:#x}"),
+        };
+
+        let response = self.send(&request).await?;
+        if let Some(error) = response.error {
+            return Err(remote_error(error));
+        }
+
+        let parse = |value: Option<String>| {
+            value
+                .as_deref()
+                .and_then(|v| Felt::from_hex(v).ok())
+                .ok_or_else(|| remote_error("missing or invalid signature".to_string()))
+        };
+        let (r, s) = (parse(response.r)?, parse(response.s)?);
+
+        match starknet_crypto::verify(&self.public_key, &hash, &r, &s) {
+            Ok(true) => Ok((r, s)),
+            _ => Err(remote_error(
+                "signature does not match public key".to_string(),
+            )),
+        }
+    }
+}
+
+fn remote_error(message: String) -> ParadexError {
+    ParadexError::SigningError(format!("Remote signer: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::InMemorySigner;
+
+    fn daemon_key() -> InMemorySigner {
+        InMemorySigner::from_hex(
+            "0x3c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc",
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_http_signer() {
+        let key = daemon_key();
+        let hash = Felt::from_hex("0x1234").unwrap();
+        let (r, s) = key.sign_hash(hash).await.unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/sign")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "public_key": format!("{:#x}", key.public_key()),
+                "hash": "0x1234",
+            })))
+            .with_body(
+                serde_json::json!({ "r": format!("{r:#x}"), "s": format!("{s:#x}") }).to_string(),
+            )
+            .create_async()
+            .await;
+
+        let signer = RemoteSigner::http(key.public_key(), format!("{}/sign", server.url()));
+        assert_eq!(signer.sign_hash(hash).await.unwrap(), (r, s));
+        mock.assert_async().await;
+
+        // A signature for another hash must be rejected
+        assert!(signer.sign_hash(Felt::ONE).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_signer_error() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/sign")
+            .with_body(r#"{"error":"key locked"}"#)
+            .create_async()
+            .await;
+
+        let signer = RemoteSigner::http(Felt::ONE, format!("{}/sign", server.url()));
+        let error = signer.sign_hash(Felt::ONE).await.unwrap_err();
+        assert!(error.to_string().contains("key locked"));
+    }
+
+    #[tokio::test]
+    async fn test_http_signer_status_error() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/sign")
+            .with_status(503)
+            .with_body("signer unavailable")
+            .create_async()
+            .await;
+
+        let signer = RemoteSigner::http(Felt::ONE, format!("{}/sign", server.url()));
+        let error = signer.sign_hash(Felt::ONE).await.unwrap_err().to_string();
+        assert!(error.contains("503"), "{error}");
+        assert!(error.contains("signer unavailable"), "{error}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_signer() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let path = std::env::temp_dir().join(format!("paradex-signer-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+
+        // Local stand-in for the signing daemon
+        let key = daemon_key();
+        let daemon = key.clone();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+
+            let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+            let hash = Felt::from_hex(request["hash"].as_str().unwrap()).unwrap();
+            let (r, s) = daemon.sign_hash(hash).await.unwrap();
+            let response = serde_json::json!({ "r": format!("{r:#x}"), "s": format!("{s:#x}") });
+            reader
+                .get_mut()
+                .write_all(format!("{response}\n").as_bytes())
+                .await
+                .unwrap();
+        });
+
+        let signer = RemoteSigner::unix(key.public_key(), &path);
+        let hash = Felt::from_hex("0xabc").unwrap();
+        assert_eq!(
+            signer.sign_hash(hash).await.unwrap(),
+            key.sign_hash(hash).await.unwrap()
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_http_signer_timeout() {
+        // Accepts connections and never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/sign", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut open = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                open.push(stream);
+            }
+        });
+
+        let signer = RemoteSigner::http(Felt::ONE, url).with_timeout(Duration::from_millis(100));
+        let error = signer.sign_hash(Felt::ONE).await.unwrap_err().to_string();
+        assert!(error.contains("timed out"), "{error}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_signer_timeout() {
+        let path = std::env::temp_dir().join(format!("paradex-silent-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let mut open = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                open.push(stream);
+            }
+        });
+
+        let signer = RemoteSigner::unix(Felt::ONE, &path).with_timeout(Duration::from_millis(100));
+        let error = signer.sign_hash(Felt::ONE).await.unwrap_err().to_string();
+        assert!(error.contains("timed out"), "{error}");
+        let _ = std::fs::remove_file(&path);
+    }
+}
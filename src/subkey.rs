@@ -5,69 +5,79 @@
 use crate::{
     api::{ApiClient, WebSocketClient},
     environment::Environment,
-    error::Result,
+    error::{ParadexError, Result},
+    keystore,
+    secret::SecretString,
+    signer::{InMemorySigner, Signer},
     types::SystemConfig,
 };
-use starknet_crypto::get_public_key;
 use starknet_types_core::felt::Felt;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, RwLock};
 
 /// Subkey account (L2-only, no L1 derivation)
+#[derive(Clone)]
 pub struct SubkeyAccount {
     pub l2_address: String,
     pub l2_public_key: Felt,
-    l2_private_key: Felt,
-    pub jwt_token: Option<String>,
+    signer: Arc<dyn Signer>,
+    jwt_token: Arc<RwLock<Option<SecretString>>>,
 }
 
 impl SubkeyAccount {
     /// Create a new subkey account
-    pub fn new(l2_private_key: &str, l2_address: &str) -> Result<Self> {
-        let private_key = Felt::from_hex(l2_private_key).map_err(|e| {
-            crate::error::ParadexError::ConfigError(format!("Invalid L2 key: {e}"))
-        })?;
-
-        let public_key = get_public_key(&private_key);
+    pub fn new(l2_private_key: impl Into<SecretString>, l2_address: &str) -> Result<Self> {
+        let signer = InMemorySigner::from_hex(l2_private_key.into().expose_secret())?;
+        Ok(Self::with_signer(Arc::new(signer), l2_address))
+    }
 
-        Ok(Self {
+    /// Create a subkey account whose key is held by `signer`
+    pub fn with_signer(signer: Arc<dyn Signer>, l2_address: &str) -> Self {
+        Self {
             l2_address: l2_address.to_string(),
-            l2_public_key: public_key,
-            l2_private_key: private_key,
-            jwt_token: None,
-        })
+            l2_public_key: signer.public_key(),
+            signer,
+            jwt_token: Arc::default(),
+        }
     }
 
-    /// Set JWT token
-    pub fn set_jwt_token(&mut self, token: impl Into<String>) {
-        self.jwt_token = Some(token.into());
+    /// Set JWT token, shared with clones of this account
+    pub fn set_jwt_token(&self, token: impl Into<SecretString>) {
+        *self.jwt_token.write().unwrap() = Some(token.into());
     }
 
     /// Get JWT token
-    pub fn get_jwt_token(&self) -> Option<&str> {
-        self.jwt_token.as_deref()
+    pub fn get_jwt_token(&self) -> Option<SecretString> {
+        self.jwt_token.read().unwrap().clone()
     }
 
-    /// Sign a message hash
-    pub fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)> {
-        let signature = starknet_crypto::sign(&self.l2_private_key, &hash, &self.l2_public_key)
-            .map_err(|e| {
-                crate::error::ParadexError::SigningError(format!("Signing failed: {e}"))
-            })?;
+    /// Sign a message hash with the account signer
+    pub async fn sign_hash(&self, hash: Felt) -> Result<(Felt, Felt)> {
+        self.signer.sign_hash(hash).await
+    }
+}
 
-        Ok((signature.r, signature.s))
+impl std::fmt::Debug for SubkeyAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubkeyAccount")
+            .field("l2_address", &self.l2_address)
+            .field("l2_public_key", &format!("{:#x}", self.l2_public_key))
+            .field("has_jwt_token", &self.jwt_token.read().unwrap().is_some())
+            .finish()
     }
 }
 
 /// ParadexSubkey client for L2-only authentication
 ///
 /// This client allows trading without L1 credentials by using a subkey account.
+/// Clones are cheap and share the same clients and account.
+#[derive(Clone)]
 pub struct ParadexSubkey {
     env: Environment,
-    api_client: Arc<Mutex<ApiClient>>,
-    ws_client: Arc<Mutex<WebSocketClient>>,
-    account: Arc<Mutex<SubkeyAccount>>,
+    api_client: Arc<ApiClient>,
+    ws_client: Arc<WebSocketClient>,
+    account: Arc<SubkeyAccount>,
     #[allow(dead_code)]
-    config: SystemConfig,
+    config: Arc<SystemConfig>,
 }
 
 impl ParadexSubkey {
@@ -94,30 +104,55 @@ impl ParadexSubkey {
     ///     Ok(())
     /// }
     /// ```
-    #[allow(clippy::await_holding_lock)]
     pub async fn new(
         env: Environment,
-        l2_private_key: impl Into<String>,
+        l2_private_key: impl Into<SecretString>,
+        l2_address: impl Into<String>,
+    ) -> Result<Self> {
+        let signer = InMemorySigner::from_hex(l2_private_key.into().expose_secret())?;
+        Self::with_signer(env, Arc::new(signer), l2_address).await
+    }
+
+    /// Create a ParadexSubkey client from an encrypted Stark keystore
+    ///
+    /// The keystore must record the L2 account address.
+    pub async fn from_keystore(
+        env: Environment,
+        path: impl AsRef<std::path::Path>,
+        passphrase: &str,
+    ) -> Result<Self> {
+        let key = keystore::load_stark_key(path, passphrase)?;
+        let l2_address = key.address.ok_or_else(|| {
+            ParadexError::ConfigError("Stark keystore has no L2 address".to_string())
+        })?;
+        let signer = InMemorySigner::new(key.private_key);
+        Self::with_signer(env, Arc::new(signer), l2_address).await
+    }
+
+    /// Create a ParadexSubkey client whose key is held by `signer`
+    pub async fn with_signer(
+        env: Environment,
+        signer: Arc<dyn Signer>,
         l2_address: impl Into<String>,
     ) -> Result<Self> {
-        let api_client = Arc::new(Mutex::new(ApiClient::new(env)?));
-        let ws_client = Arc::new(Mutex::new(WebSocketClient::new(env)));
+        let mut api_client = ApiClient::new(env.clone())?;
+        let mut ws_client = WebSocketClient::new(env.clone());
+        let session = Arc::default();
+        api_client.set_auth_session(Arc::clone(&session));
+        ws_client.set_auth_session(session);
 
         // Fetch system config
-        let config = {
-            let client = api_client.lock().unwrap();
-            client.fetch_system_config().await?
-        };
+        let config = api_client.fetch_system_config().await?;
 
         // Create subkey account
-        let account = SubkeyAccount::new(&l2_private_key.into(), &l2_address.into())?;
+        let account = SubkeyAccount::with_signer(signer, &l2_address.into());
 
         let subkey = Self {
             env,
-            api_client,
-            ws_client,
-            account: Arc::new(Mutex::new(account)),
-            config,
+            api_client: Arc::new(api_client),
+            ws_client: Arc::new(ws_client),
+            account: Arc::new(account),
+            config: Arc::new(config),
         };
 
         // Authenticate
@@ -128,21 +163,21 @@ impl ParadexSubkey {
 
     /// Get the environment
     pub fn environment(&self) -> Environment {
-        self.env
+        self.env.clone()
     }
 
     /// Get API client reference
-    pub fn api_client(&self) -> Arc<Mutex<ApiClient>> {
+    pub fn api_client(&self) -> Arc<ApiClient> {
         Arc::clone(&self.api_client)
     }
 
     /// Get WebSocket client reference
-    pub fn ws_client(&self) -> Arc<Mutex<WebSocketClient>> {
+    pub fn ws_client(&self) -> Arc<WebSocketClient> {
         Arc::clone(&self.ws_client)
     }
 
     /// Get account reference
-    pub fn account(&self) -> Arc<Mutex<SubkeyAccount>> {
+    pub fn account(&self) -> Arc<SubkeyAccount> {
         Arc::clone(&self.account)
     }
 
@@ -167,4 +202,25 @@ mod tests {
         let account = SubkeyAccount::new(private_key, address);
         assert!(account.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_subkey_account_signs_with_signer() {
+        let signer = InMemorySigner::from_hex("0x1234").unwrap();
+        let account = SubkeyAccount::with_signer(Arc::new(signer.clone()), "0x5678");
+        assert_eq!(account.l2_public_key, signer.public_key());
+
+        let hash = Felt::from_hex("0xabc").unwrap();
+        let (r, s) = account.sign_hash(hash).await.unwrap();
+        assert!(starknet_crypto::verify(&account.l2_public_key, &hash, &r, &s).unwrap());
+    }
+
+    #[test]
+    fn test_subkey_account_debug_redacts_secrets() {
+        let account = SubkeyAccount::new("0xdeadbeefcafe", "0x5678").unwrap();
+        account.set_jwt_token("eyJhbGciOiJIUzI1NiJ9.secret");
+
+        let debug = format!("{account:?}");
+        assert!(!debug.contains("deadbeefcafe"));
+        assert!(!debug.contains("eyJhbGciOiJIUzI1NiJ9"));
+    }
 }
@@ -1,3 +1,4 @@
+use super::{BlockOfferStatus, BlockTradeStatus, Extra};
 use serde::{Deserialize, Serialize};
 
 /// Block trade request
@@ -36,12 +37,14 @@ pub struct BlockExecuteRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockTradeDetail {
     pub block_id: String,
-    pub status: String,
+    pub status: BlockTradeStatus,
     pub markets: Vec<String>,
     pub initiator: String,
     pub required_signers: Vec<String>,
     pub created_at: i64,
-    pub updated_at: i64,
+    pub updated_at: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Block trade offer detail
@@ -51,6 +54,8 @@ pub struct BlockOfferDetail {
     pub block_id: String,
     pub account: String,
     pub orders: Vec<BlockOfferOrder>,
-    pub status: String,
+    pub status: BlockOfferStatus,
     pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -0,0 +1,290 @@
+//! Serde adapters for the numeric fields of response models
+//!
+//! Paradex sends prices, sizes and USD values as JSON strings, and a few
+//! endpoints as plain numbers. Both are accepted, values are serialized back
+//! as the text received.
+
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{cmp::Ordering, fmt, hash, ops::Deref, str::FromStr};
+
+/// Decimal field of a response model, with the text it was read from
+///
+/// Dereferences to the [`Decimal`] value. [`Amount::as_str`] and `Display`
+/// give the text exactly as received, for echoing values back byte for byte.
+/// Numbers sent as JSON numbers are kept in their shortest form. Comparison
+/// and hashing use the value only, so `"1.50"` equals `"1.5"`.
+#[derive(Debug, Clone)]
+pub struct Amount {
+    value: Decimal,
+    text: String,
+}
+
+impl Amount {
+    /// Decimal value
+    pub fn value(&self) -> Decimal {
+        self.value
+    }
+
+    /// Text as received from the API
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Deref for Amount {
+    type Target = Decimal;
+
+    fn deref(&self) -> &Decimal {
+        &self.value
+    }
+}
+
+impl From<Decimal> for Amount {
+    fn from(value: Decimal) -> Self {
+        Amount {
+            value,
+            text: value.to_string(),
+        }
+    }
+}
+
+impl From<Amount> for Decimal {
+    fn from(amount: Amount) -> Self {
+        amount.value
+    }
+}
+
+impl FromStr for Amount {
+    type Err = rust_decimal::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let value = Decimal::from_str(text).or_else(|_| Decimal::from_scientific(text))?;
+        Ok(Amount {
+            value,
+            text: text.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl PartialEq for Amount {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Amount {}
+
+impl PartialEq<Decimal> for Amount {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialOrd for Amount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Amount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl hash::Hash for Amount {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AmountVisitor)
+    }
+}
+
+fn parse<E: de::Error>(text: &str) -> Result<Amount, E> {
+    text.parse()
+        .map_err(|e| E::custom(format!("invalid decimal {text:?}: {e}")))
+}
+
+struct AmountVisitor;
+
+impl de::Visitor<'_> for AmountVisitor {
+    type Value = Amount;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a decimal as a string or a number")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Amount, E> {
+        parse(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Amount, E> {
+        parse(&v.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Amount, E> {
+        parse(&v.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Amount, E> {
+        parse(&v.to_string())
+    }
+}
+
+pub fn serialize<S: Serializer>(value: &Amount, serializer: S) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+    Amount::deserialize(deserializer)
+}
+
+/// Adapter for optional fields, reading `null` and `""` as `None`
+///
+/// Pair it with `#[serde(default)]` so missing fields are accepted too.
+pub mod option {
+    use super::{Amount, AmountVisitor};
+    use serde::{de, Deserializer, Serializer};
+    use std::fmt;
+
+    struct OptionVisitor;
+
+    impl<'de> de::Visitor<'de> for OptionVisitor {
+        type Value = Option<Amount>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a decimal as a string or a number, or null")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+            d.deserialize_any(self)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            if v.is_empty() {
+                Ok(None)
+            } else {
+                de::Visitor::visit_str(AmountVisitor, v).map(Some)
+            }
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            de::Visitor::visit_i64(AmountVisitor, v).map(Some)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            de::Visitor::visit_u64(AmountVisitor, v).map(Some)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            de::Visitor::visit_f64(AmountVisitor, v).map(Some)
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Amount>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_str(value.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Amount>, D::Error> {
+        deserializer.deserialize_option(OptionVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Sample {
+        #[serde(with = "super")]
+        price: Amount,
+        #[serde(with = "option", default)]
+        mark: Option<Amount>,
+    }
+
+    fn sample(value: serde_json::Value) -> Sample {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_accepts_strings_and_numbers() {
+        assert_eq!(
+            sample(json!({"price": "42000.50"})).price,
+            Decimal::new(4200050, 2)
+        );
+        assert_eq!(sample(json!({"price": 42000})).price, Decimal::from(42000));
+        assert_eq!(sample(json!({"price": 0.25})).price, Decimal::new(25, 2));
+        assert_eq!(sample(json!({"price": "1e-3"})).price, Decimal::new(1, 3));
+        assert!(serde_json::from_value::<Sample>(json!({"price": "abc"})).is_err());
+
+        // Trailing zeros survive the round trip
+        assert_eq!(
+            serde_json::to_value(sample(json!({"price": "1.500", "mark": "2"}))).unwrap(),
+            json!({"price": "1.500", "mark": "2"})
+        );
+    }
+
+    #[test]
+    fn test_keeps_raw_text() {
+        let price = sample(json!({"price": "0042.50"})).price;
+        assert_eq!(price.as_str(), "0042.50");
+        assert_eq!(price.to_string(), "0042.50");
+        assert_eq!(price.value(), Decimal::new(4250, 2));
+        assert_eq!(*price + Decimal::ONE, Decimal::new(4350, 2));
+
+        assert_eq!(sample(json!({"price": 0.25})).price.as_str(), "0.25");
+        assert_eq!(sample(json!({"price": "1e-3"})).price.as_str(), "1e-3");
+        assert_eq!(
+            serde_json::to_value(sample(json!({"price": "0042.50"}))).unwrap(),
+            json!({"price": "0042.50", "mark": null})
+        );
+    }
+
+    #[test]
+    fn test_optional_values() {
+        assert!(sample(json!({"price": "1"})).mark.is_none());
+        assert!(sample(json!({"price": "1", "mark": null})).mark.is_none());
+        assert!(sample(json!({"price": "1", "mark": ""})).mark.is_none());
+        assert_eq!(
+            sample(json!({"price": "1", "mark": 3}))
+                .mark
+                .unwrap()
+                .to_string(),
+            "3"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+/// Enum over the string values of an API field, with an `Unknown` catch-all
+/// so values added by the server later still deserialize
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// Value not known to this SDK version
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Value as sent by the API
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Unknown(other.to_string()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+pub(crate) use string_enum;
+
+string_enum! {
+    /// Lifecycle state of an order
+    pub enum OrderStatus {
+        /// Received, not yet on the book
+        New => "NEW",
+        /// Resting on the book
+        Open => "OPEN",
+        /// Filled, cancelled or rejected
+        Closed => "CLOSED",
+        /// Conditional order waiting for its trigger price
+        Untriggered => "UNTRIGGERED",
+    }
+}
+
+string_enum! {
+    /// Side of a fill in the match
+    pub enum LiquidityRole {
+        Maker => "MAKER",
+        Taker => "TAKER",
+    }
+}
+
+string_enum! {
+    /// Direction of a position
+    pub enum PositionSide {
+        Long => "LONG",
+        Short => "SHORT",
+    }
+}
+
+string_enum! {
+    /// Trading state of a market
+    pub enum MarketStatus {
+        Open => "OPEN",
+        /// Only orders adding liquidity are accepted
+        PostOnly => "POST_ONLY",
+        /// Only cancels are accepted
+        CancelOnly => "CANCEL_ONLY",
+        Closed => "CLOSED",
+    }
+}
+
+string_enum! {
+    /// Right granted by an option
+    pub enum OptionType {
+        Call => "CALL",
+        Put => "PUT",
+    }
+}
+
+string_enum! {
+    /// Progress of a deposit, withdrawal or internal transfer
+    pub enum TransferStatus {
+        Pending => "PENDING",
+        /// Credited, waiting to be claimed on L1
+        Available => "AVAILABLE",
+        Completed => "COMPLETED",
+        Failed => "FAILED",
+    }
+}
+
+string_enum! {
+    /// State of a block trade
+    pub enum BlockTradeStatus {
+        Created => "CREATED",
+        /// Collecting offers from the required signers
+        OfferCollection => "OFFER_COLLECTION",
+        ReadyToExecute => "READY_TO_EXECUTE",
+        Executing => "EXECUTING",
+        Completed => "COMPLETED",
+        Cancelled => "CANCELLED",
+        Expired => "EXPIRED",
+    }
+}
+
+string_enum! {
+    /// State of an offer on a block trade
+    pub enum BlockOfferStatus {
+        Created => "CREATED",
+        Accepted => "ACCEPTED",
+        Executed => "EXECUTED",
+        Cancelled => "CANCELLED",
+        Expired => "EXPIRED",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_and_unknown_values() {
+        let status: OrderStatus = serde_json::from_str(r#""UNTRIGGERED""#).unwrap();
+        assert_eq!(status, OrderStatus::Untriggered);
+
+        let status: OrderStatus = serde_json::from_str(r#""PARTIALLY_CLOSED""#).unwrap();
+        assert_eq!(status, OrderStatus::Unknown("PARTIALLY_CLOSED".to_string()));
+        assert_eq!(status.to_string(), "PARTIALLY_CLOSED");
+        assert_eq!(
+            serde_json::to_string(&status).unwrap(),
+            r#""PARTIALLY_CLOSED""#
+        );
+
+        assert_eq!(LiquidityRole::from("MAKER"), LiquidityRole::Maker);
+        assert_eq!(TransferStatus::Available.as_str(), "AVAILABLE");
+    }
+}
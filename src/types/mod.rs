@@ -1,7 +1,16 @@
 pub mod block_trades;
+pub mod decimal;
+pub mod enums;
 pub mod models;
 pub mod order;
+pub mod query;
 
 pub use block_trades::*;
+pub use decimal::Amount;
+pub use enums::*;
 pub use models::*;
 pub use order::*;
+pub use query::{
+    FillsQuery, FundingQuery, HistoryQuery, OrdersHistoryQuery, PageQuery, Paged, TradesQuery,
+    TransfersQuery,
+};
@@ -1,4 +1,45 @@
+use super::{
+    decimal::{self, Amount},
+    LiquidityRole, MarketStatus, OptionType, OrderSide, OrderStatus, OrderType, PositionSide,
+    TransferStatus,
+};
+use crate::secret::SecretString;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Fields of a response not modelled by this SDK version
+///
+/// Kept so data added by the exchange is not lost. With the `strict` feature
+/// any such field fails deserialization instead, to catch schema drift in CI.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Extra(pub Map<String, Value>);
+
+impl std::ops::Deref for Extra {
+    type Target = Map<String, Value>;
+
+    fn deref(&self) -> &Map<String, Value> {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for Extra {
+    fn deref_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for Extra {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = Map::deserialize(deserializer)?;
+        #[cfg(feature = "strict")]
+        if let Some(field) = fields.keys().next() {
+            return Err(serde::de::Error::custom(format!("unknown field `{field}`")));
+        }
+        Ok(Extra(fields))
+    }
+}
 
 /// System configuration from Paradex API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +52,8 @@ pub struct SystemConfig {
     pub paraclear_account_hash: String,
     pub paraclear_decimals: u32,
     pub bridged_tokens: Vec<BridgedToken>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,67 +64,248 @@ pub struct BridgedToken {
     pub l2_bridge_address: String,
     pub decimals: u32,
     pub symbol: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Exchange status from `GET /system/state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemState {
+    /// `ok`, `maintenance` or `cancel_only`
+    pub status: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Server clock from `GET /system/time`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerTime {
+    /// Milliseconds since the Unix epoch
+    pub server_time: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Account summary response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountSummary {
     pub account: String,
-    pub equity_usd: String,
-    pub notional_usd: String,
-    pub total_pnl_usd: String,
-    pub total_upnl_usd: String,
-    pub total_rpnl_usd: String,
-    pub margin_balance_usd: String,
-    pub portfolio_initial_margin_requirement_usd: String,
-    pub portfolio_maintenance_margin_requirement_usd: String,
-    pub leverage: String,
-    pub available_balance_usd: String,
-    pub withdrawable_balance_usd: String,
-    pub buying_power_usd: String,
+    #[serde(with = "decimal")]
+    pub equity_usd: Amount,
+    #[serde(with = "decimal")]
+    pub notional_usd: Amount,
+    #[serde(with = "decimal")]
+    pub total_pnl_usd: Amount,
+    #[serde(with = "decimal")]
+    pub total_upnl_usd: Amount,
+    #[serde(with = "decimal")]
+    pub total_rpnl_usd: Amount,
+    #[serde(with = "decimal")]
+    pub margin_balance_usd: Amount,
+    #[serde(with = "decimal")]
+    pub portfolio_initial_margin_requirement_usd: Amount,
+    #[serde(with = "decimal")]
+    pub portfolio_maintenance_margin_requirement_usd: Amount,
+    #[serde(with = "decimal")]
+    pub leverage: Amount,
+    #[serde(with = "decimal")]
+    pub available_balance_usd: Amount,
+    #[serde(with = "decimal")]
+    pub withdrawable_balance_usd: Amount,
+    #[serde(with = "decimal")]
+    pub buying_power_usd: Amount,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Authentication response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AuthResponse {
-    pub jwt_token: String,
+    pub jwt_token: SecretString,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-/// Market information
+/// Market definition from `GET /markets`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Market {
     pub symbol: String,
     pub base_currency: String,
     pub quote_currency: String,
-    pub price_tick_size: String,
-    pub quantity_tick_size: String,
-    pub min_quantity: String,
-    pub max_quantity: String,
-    pub max_market_order_size: String,
-    pub max_leverage: String,
-    pub status: String,
+    pub settlement_currency: String,
+    /// Contract traded, read from `asset_kind` and the option fields
+    #[serde(flatten)]
+    pub kind: MarketKind,
+    /// Margining of the market, such as `cross`, sent as `market_kind`
+    #[serde(rename = "market_kind")]
+    pub margin_kind: Option<String>,
+    #[serde(with = "decimal")]
+    pub price_tick_size: Amount,
+    #[serde(with = "decimal", alias = "order_size_increment")]
+    pub quantity_tick_size: Amount,
+    #[serde(with = "decimal::option", default)]
+    pub min_quantity: Option<Amount>,
+    #[serde(with = "decimal::option", default, alias = "max_order_size")]
+    pub max_quantity: Option<Amount>,
+    /// Smallest order value accepted, in the quote currency
+    #[serde(with = "decimal::option", default)]
+    pub min_notional: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub max_market_order_size: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub max_leverage: Option<Amount>,
+    pub status: Option<MarketStatus>,
+    /// Largest position an account may hold, in the base currency
+    #[serde(with = "decimal::option", default)]
+    pub position_limit: Option<Amount>,
+    /// Maximum distance of a limit price from the mark price, as a fraction
+    #[serde(with = "decimal::option", default)]
+    pub price_bands_width: Option<Amount>,
+    pub max_open_orders: Option<u32>,
+    #[serde(with = "decimal::option", default)]
+    pub funding_period_hours: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub interest_rate: Option<Amount>,
+    /// Bound applied to the funding premium around the interest rate
+    #[serde(with = "decimal::option", default)]
+    pub clamp_rate: Option<Amount>,
+    /// Milliseconds since the Unix epoch
+    pub open_at: Option<i64>,
+    /// Milliseconds since the Unix epoch, 0 for contracts that never expire
+    pub expiry_at: Option<i64>,
+    pub delta1_cross_margin_params: Option<Delta1CrossMarginParams>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Contract traded on a market
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarketKind {
+    /// Perpetual future
+    Perp,
+    /// Perpetual option
+    Option {
+        strike_price: Amount,
+        option_type: OptionType,
+        /// Maximum distance of a limit price's implied volatility from the
+        /// mark's
+        iv_bands_width: Option<Amount>,
+    },
+    /// Asset kind not known to this SDK version
+    Unknown(String),
+}
+
+/// Fields of a market payload [`MarketKind`] is read from
+#[derive(Deserialize)]
+struct MarketKindFields {
+    asset_kind: String,
+    #[serde(with = "decimal::option", default)]
+    strike_price: Option<Amount>,
+    #[serde(default)]
+    option_type: Option<OptionType>,
+    #[serde(with = "decimal::option", default)]
+    iv_bands_width: Option<Amount>,
+}
+
+impl Serialize for MarketKind {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            MarketKind::Perp => map.serialize_entry("asset_kind", "PERP")?,
+            MarketKind::Option {
+                strike_price,
+                option_type,
+                iv_bands_width,
+            } => {
+                map.serialize_entry("asset_kind", "PERP_OPTION")?;
+                map.serialize_entry("strike_price", &strike_price.to_string())?;
+                map.serialize_entry("option_type", option_type)?;
+                if let Some(iv_bands_width) = iv_bands_width {
+                    map.serialize_entry("iv_bands_width", &iv_bands_width.to_string())?;
+                }
+            }
+            MarketKind::Unknown(asset_kind) => map.serialize_entry("asset_kind", asset_kind)?,
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MarketKind {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let fields = MarketKindFields::deserialize(deserializer)?;
+        match fields.asset_kind.as_str() {
+            "PERP" => Ok(MarketKind::Perp),
+            "PERP_OPTION" => {
+                let missing =
+                    |field| serde::de::Error::custom(format!("option market without `{field}`"));
+                Ok(MarketKind::Option {
+                    strike_price: fields.strike_price.ok_or_else(|| missing("strike_price"))?,
+                    option_type: fields.option_type.ok_or_else(|| missing("option_type"))?,
+                    iv_bands_width: fields.iv_bands_width,
+                })
+            }
+            _ => Ok(MarketKind::Unknown(fields.asset_kind)),
+        }
+    }
+}
+
+/// Parameters of the initial and maintenance margin fractions of a
+/// perpetual future under cross margin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delta1CrossMarginParams {
+    #[serde(with = "decimal")]
+    pub imf_base: Amount,
+    #[serde(with = "decimal")]
+    pub imf_factor: Amount,
+    #[serde(with = "decimal")]
+    pub imf_shift: Amount,
+    #[serde(with = "decimal")]
+    pub mmf_factor: Amount,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Market summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketSummary {
     pub symbol: String,
-    pub last_price: Option<String>,
-    pub index_price: Option<String>,
-    pub mark_price: Option<String>,
-    pub high_24h: Option<String>,
-    pub low_24h: Option<String>,
-    pub volume_24h: Option<String>,
-    pub open_interest: Option<String>,
-    pub funding_rate: Option<String>,
+    #[serde(with = "decimal::option", default)]
+    pub last_price: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub index_price: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub mark_price: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub high_24h: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub low_24h: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub volume_24h: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub open_interest: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub funding_rate: Option<Amount>,
     pub next_funding_at: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Order book entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBookEntry {
-    pub price: String,
-    pub size: String,
+    #[serde(with = "decimal")]
+    pub price: Amount,
+    #[serde(with = "decimal")]
+    pub size: Amount,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Order book
@@ -91,16 +315,178 @@ pub struct OrderBook {
     pub asks: Vec<OrderBookEntry>,
     pub seq_no: i64,
     pub timestamp: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Best bid/offer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BBO {
-    pub bid: Option<String>,
-    pub bid_size: Option<String>,
-    pub ask: Option<String>,
-    pub ask_size: Option<String>,
+    #[serde(with = "decimal::option", default)]
+    pub bid: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub bid_size: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub ask: Option<Amount>,
+    #[serde(with = "decimal::option", default)]
+    pub ask_size: Option<Amount>,
     pub timestamp: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Public trade from `GET /trades`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trade {
+    pub id: String,
+    pub market: String,
+    pub side: OrderSide,
+    #[serde(with = "decimal")]
+    pub price: Amount,
+    #[serde(with = "decimal")]
+    pub size: Amount,
+    /// `FILL`, `LIQUIDATION`, `TRANSFER` or `SETTLE_MARKET`
+    pub trade_type: String,
+    pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Funding index sample from `GET /funding/data`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingData {
+    pub market: String,
+    #[serde(with = "decimal")]
+    pub funding_index: Amount,
+    #[serde(with = "decimal")]
+    pub funding_premium: Amount,
+    #[serde(with = "decimal")]
+    pub funding_rate: Amount,
+    pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Insurance fund state from `GET /insurance`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsuranceFund {
+    pub account: String,
+    #[serde(with = "decimal")]
+    pub account_value: Amount,
+    pub settlement_asset: String,
+    pub updated_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// OHLCV candle, sent by `GET /markets/klines` as a
+/// `[timestamp, open, high, low, close, volume]` array
+///
+/// Has no [`Extra`]: the row is positional, so a row with more elements
+/// fails to decode rather than losing them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "KlineRow", into = "KlineRow")]
+pub struct Kline {
+    /// Candle start in milliseconds since the Unix epoch
+    pub timestamp: i64,
+    pub open: Amount,
+    pub high: Amount,
+    pub low: Amount,
+    pub close: Amount,
+    pub volume: Amount,
+}
+
+/// Row as sent by the API
+type KlineRow = (i64, Amount, Amount, Amount, Amount, Amount);
+
+impl From<KlineRow> for Kline {
+    fn from((timestamp, open, high, low, close, volume): KlineRow) -> Self {
+        Self {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+}
+
+impl From<Kline> for KlineRow {
+    fn from(kline: Kline) -> Self {
+        (
+            kline.timestamp,
+            kline.open,
+            kline.high,
+            kline.low,
+            kline.close,
+            kline.volume,
+        )
+    }
+}
+
+/// Account profile from `GET /account/profile`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountProfile {
+    pub username: Option<String>,
+    #[serde(default)]
+    pub is_username_private: bool,
+    pub referral_code: Option<String>,
+    pub referred_by: Option<String>,
+    /// Maximum slippage accepted for market orders, per market
+    #[serde(default)]
+    pub market_max_slippage: HashMap<String, String>,
+    pub twitter: Option<TwitterProfile>,
+    #[serde(default)]
+    pub nfts: Vec<Value>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Twitter account linked to a profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterProfile {
+    pub id: String,
+    pub username: String,
+    pub image_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Account details from `GET /account/info` and `GET /account/subaccounts`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub account: String,
+    /// `main`, `subaccount` or `vault`
+    pub kind: Option<String>,
+    pub parent_account: Option<String>,
+    pub public_key: Option<String>,
+    pub derivation_path: Option<String>,
+    /// Market the account is restricted to, for isolated margin accounts
+    pub isolated_market: Option<String>,
+    pub username: Option<String>,
+    pub created_at: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Liquidation of the account from `GET /liquidations`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Liquidation {
+    pub id: String,
+    pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Fill reverted by the exchange, from `GET /tradebusts`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tradebust {
+    pub account: String,
+    pub busted_fill_id: String,
+    pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Fill information
@@ -111,13 +497,18 @@ pub struct Fill {
     pub market: String,
     pub order_id: String,
     pub client_id: Option<String>,
-    pub side: String,
-    pub price: String,
-    pub size: String,
-    pub fee: String,
+    pub side: OrderSide,
+    #[serde(with = "decimal")]
+    pub price: Amount,
+    #[serde(with = "decimal")]
+    pub size: Amount,
+    #[serde(with = "decimal")]
+    pub fee: Amount,
     pub trade_id: String,
-    pub liquidity_role: String,
+    pub liquidity_role: LiquidityRole,
     pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Position information
@@ -125,23 +516,37 @@ pub struct Fill {
 pub struct Position {
     pub account: String,
     pub market: String,
-    pub side: String,
-    pub size: String,
-    pub entry_price: String,
-    pub mark_price: String,
-    pub liquidation_price: Option<String>,
-    pub unrealized_pnl: String,
-    pub realized_pnl: String,
-    pub margin: String,
-    pub leverage: String,
+    pub side: PositionSide,
+    #[serde(with = "decimal")]
+    pub size: Amount,
+    #[serde(with = "decimal")]
+    pub entry_price: Amount,
+    #[serde(with = "decimal")]
+    pub mark_price: Amount,
+    #[serde(with = "decimal::option", default)]
+    pub liquidation_price: Option<Amount>,
+    #[serde(with = "decimal")]
+    pub unrealized_pnl: Amount,
+    #[serde(with = "decimal")]
+    pub realized_pnl: Amount,
+    #[serde(with = "decimal")]
+    pub margin: Amount,
+    #[serde(with = "decimal")]
+    pub leverage: Amount,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Balance information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Balance {
     pub token: String,
-    pub available: String,
-    pub locked: String,
+    #[serde(with = "decimal")]
+    pub available: Amount,
+    #[serde(with = "decimal")]
+    pub locked: Amount,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Transaction information
@@ -153,6 +558,8 @@ pub struct Transaction {
     pub amount: String,
     pub status: String,
     pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Transfer information
@@ -163,8 +570,10 @@ pub struct Transfer {
     pub r#type: String,
     pub amount: String,
     pub token: String,
-    pub status: String,
+    pub status: TransferStatus,
     pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Funding payment
@@ -173,10 +582,15 @@ pub struct FundingPayment {
     pub id: String,
     pub account: String,
     pub market: String,
-    pub payment: String,
-    pub position_size: String,
-    pub rate: String,
+    #[serde(with = "decimal")]
+    pub payment: Amount,
+    #[serde(with = "decimal")]
+    pub position_size: Amount,
+    #[serde(with = "decimal")]
+    pub rate: Amount,
     pub created_at: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Points data
@@ -185,6 +599,8 @@ pub struct PointsData {
     pub market: String,
     pub program: String,
     pub points: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Paginated response wrapper
@@ -193,9 +609,14 @@ pub struct PaginatedResponse<T> {
     pub results: Vec<T>,
     pub next: Option<String>,
     pub prev: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Generic API response
+///
+/// Has no [`Extra`] of its own: every field is passed to the flattened `T`,
+/// whose `extra` keeps the ones it doesn't model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     #[serde(flatten)]
@@ -209,16 +630,23 @@ pub struct OrderResponse {
     pub client_id: Option<String>,
     pub account: String,
     pub market: String,
-    pub side: String,
-    pub r#type: String,
-    pub price: Option<String>,
-    pub size: String,
-    pub filled_size: String,
-    pub remaining_size: String,
-    pub status: String,
-    pub signature: String,
+    pub side: OrderSide,
+    pub r#type: OrderType,
+    #[serde(with = "decimal::option", default)]
+    pub price: Option<Amount>,
+    #[serde(with = "decimal")]
+    pub size: Amount,
+    #[serde(with = "decimal")]
+    pub filled_size: Amount,
+    #[serde(with = "decimal")]
+    pub remaining_size: Amount,
+    pub status: OrderStatus,
+    /// Missing on orders not placed through the API, such as liquidations
+    pub signature: Option<String>,
     pub created_at: i64,
-    pub updated_at: i64,
+    pub updated_at: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Batch order response
@@ -226,6 +654,8 @@ pub struct OrderResponse {
 pub struct BatchOrderResponse {
     pub orders: Vec<OrderResponse>,
     pub errors: Vec<OrderError>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Order error
@@ -233,4 +663,265 @@ pub struct BatchOrderResponse {
 pub struct OrderError {
     pub client_id: Option<String>,
     pub error: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Optional fields sent when onboarding an account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OnboardingOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referral_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub marketing_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utm: Option<Utm>,
+}
+
+/// UTM attribution attached to onboarding
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Utm {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub campaign: Option<String>,
+}
+
+/// Onboarding status response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingStatus {
+    #[serde(default)]
+    pub address: Option<String>,
+    pub exists: bool,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Result of an onboarding call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingOutcome {
+    /// The account was onboarded by this call
+    Created,
+    /// The account was onboarded before
+    AlreadyOnboarded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use serde::de::DeserializeOwned;
+
+    /// Payloads per endpoint, with their origin under `_source`
+    const RESPONSES: &str = include_str!("../../tests/vectors/responses.json");
+
+    /// Decode the recorded payload of `endpoint`
+    fn recorded<T: DeserializeOwned>(endpoint: &str) -> T {
+        let responses: Value = serde_json::from_str(RESPONSES).unwrap();
+        serde_json::from_value(responses[endpoint].clone())
+            .unwrap_or_else(|e| panic!("{endpoint}: {e}"))
+    }
+
+    #[test]
+    fn test_system_models() {
+        let state: SystemState = recorded("system/state");
+        assert_eq!(state.status, "ok");
+        let time: ServerTime = recorded("system/time");
+        assert_eq!(time.server_time, 1_700_000_000_123);
+    }
+
+    #[test]
+    fn test_market_data_models() {
+        let trades: PaginatedResponse<Trade> = recorded("trades");
+        assert_eq!(trades.results.len(), 2);
+        assert_eq!(trades.results[0].trade_type, "FILL");
+        assert_eq!(trades.results[1].side, OrderSide::Sell);
+        assert_eq!(trades.results[1].price, Decimal::new(370498, 1));
+        assert!(trades.next.is_some());
+
+        let funding: PaginatedResponse<FundingData> = recorded("funding/data");
+        assert_eq!(funding.results[0].funding_rate, Decimal::new(125, 7));
+        assert_eq!(funding.results[0].funding_index.as_str(), "1821.3459087");
+
+        let insurance: InsuranceFund = recorded("insurance");
+        assert_eq!(insurance.settlement_asset, "USDC");
+        assert_eq!(insurance.account_value.as_str(), "136285.06918911");
+
+        let klines: PaginatedResponse<Kline> = recorded("markets/klines");
+        assert_eq!(
+            klines.results[0],
+            Kline {
+                timestamp: 1_700_000_000_000,
+                open: Decimal::new(370005, 1).into(),
+                high: Decimal::from(37100).into(),
+                low: Decimal::new(3695025, 2).into(),
+                close: Decimal::from(37050).into(),
+                volume: Decimal::new(12345, 3).into(),
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&klines.results[0]).unwrap(),
+            serde_json::json!([
+                1_700_000_000_000_i64,
+                "37000.5",
+                "37100",
+                "36950.25",
+                "37050",
+                "12.345"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_market_models() {
+        let markets: PaginatedResponse<Market> = recorded("markets");
+        let [perp, option] = &markets.results[..] else {
+            panic!("expected two markets");
+        };
+
+        assert_eq!(perp.kind, MarketKind::Perp);
+        assert_eq!(perp.margin_kind.as_deref(), Some("cross"));
+        assert_eq!(serde_json::to_value(perp).unwrap()["market_kind"], "cross");
+        assert_eq!(perp.quantity_tick_size.to_string(), "0.001");
+        assert_eq!(perp.max_quantity.as_ref().unwrap().to_string(), "100");
+        assert_eq!(perp.max_open_orders, Some(100));
+        assert_eq!(perp.funding_period_hours.as_ref().unwrap().to_string(), "8");
+        assert_eq!(perp.expiry_at, Some(0));
+        let margin = perp.delta1_cross_margin_params.as_ref().unwrap();
+        assert_eq!(margin.imf_base.to_string(), "0.11");
+
+        match &option.kind {
+            MarketKind::Option {
+                strike_price,
+                option_type,
+                iv_bands_width,
+            } => {
+                assert_eq!(strike_price.to_string(), "60000");
+                assert_eq!(*option_type, OptionType::Call);
+                assert_eq!(iv_bands_width.as_ref().unwrap().to_string(), "0.05");
+            }
+            other => panic!("unexpected market kind: {other:?}"),
+        }
+
+        // The kind is written back as the fields it was read from
+        let echoed = serde_json::to_value(option).unwrap();
+        assert_eq!(echoed["asset_kind"], "PERP_OPTION");
+        assert_eq!(echoed["option_type"], "CALL");
+        assert_eq!(echoed["strike_price"], "60000");
+
+        let unknown: Market = serde_json::from_value(serde_json::json!({
+            "symbol": "BTC-USD-FUT",
+            "base_currency": "BTC",
+            "quote_currency": "USD",
+            "settlement_currency": "USDC",
+            "asset_kind": "FUTURE",
+            "price_tick_size": "1",
+            "order_size_increment": "0.001",
+        }))
+        .unwrap();
+        assert_eq!(unknown.kind, MarketKind::Unknown("FUTURE".to_string()));
+    }
+
+    #[test]
+    fn test_account_models() {
+        let profile: AccountProfile = recorded("account/profile");
+        assert_eq!(profile.username.as_deref(), Some("satoshi"));
+        assert_eq!(profile.market_max_slippage["BTC-USD-PERP"], "0.05");
+        assert_eq!(profile.twitter.unwrap().username, "satoshi_trades");
+
+        let info: AccountInfo = recorded("account/info");
+        assert_eq!(info.kind.as_deref(), Some("main"));
+        assert!(info.parent_account.is_none());
+
+        let subaccounts: PaginatedResponse<AccountInfo> = recorded("account/subaccounts");
+        let subaccount = &subaccounts.results[0];
+        assert_eq!(subaccount.kind.as_deref(), Some("subaccount"));
+        assert_eq!(
+            subaccount.parent_account.as_deref(),
+            Some(info.account.as_str())
+        );
+        assert_eq!(subaccount.isolated_market.as_deref(), Some("ETH-USD-PERP"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_unknown_fields_preserved() {
+        let payload = serde_json::json!({
+            "bid": "37000.1",
+            "bid_size": "1.5",
+            "ask": null,
+            "timestamp": 1_700_000_000_000_i64,
+            "market": "BTC-USD-PERP",
+            "seq_no": 42,
+        });
+        let bbo: BBO = serde_json::from_value(payload.clone()).unwrap();
+        assert!(bbo.ask.is_none() && bbo.ask_size.is_none());
+        assert_eq!(bbo.extra["market"], "BTC-USD-PERP");
+        assert_eq!(bbo.extra["seq_no"], 42);
+
+        let echoed = serde_json::to_value(&bbo).unwrap();
+        assert_eq!(echoed["market"], payload["market"]);
+        assert_eq!(echoed["seq_no"], payload["seq_no"]);
+
+        let page: PaginatedResponse<BBO> = serde_json::from_value(serde_json::json!({
+            "results": [],
+            "next": null,
+            "prev": null,
+            "total": 3,
+        }))
+        .unwrap();
+        assert_eq!(page.extra["total"], 3);
+
+        let wrapped: ApiResponse<ServerTime> = serde_json::from_value(serde_json::json!({
+            "server_time": 1,
+            "timezone": "UTC",
+        }))
+        .unwrap();
+        assert_eq!(wrapped.data.extra["timezone"], "UTC");
+    }
+
+    #[test]
+    fn test_kline_rejects_extra_columns() {
+        let row = serde_json::json!([1_700_000_000_000_i64, "1", "2", "0.5", "1.5", "10", "7"]);
+        assert!(serde_json::from_value::<Kline>(row).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    fn test_unknown_fields_rejected() {
+        let error = serde_json::from_value::<ServerTime>(serde_json::json!({
+            "server_time": 1,
+            "timezone": "UTC",
+        }))
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown field `timezone`"));
+    }
+
+    #[test]
+    fn test_history_models() {
+        let liquidations: PaginatedResponse<Liquidation> = recorded("liquidations");
+        assert_eq!(liquidations.results[0].created_at, 1_699_990_000_000);
+
+        let tradebusts: PaginatedResponse<Tradebust> = recorded("tradebusts");
+        assert_eq!(
+            tradebusts.results[0].busted_fill_id,
+            "1700000000000201601010000001"
+        );
+    }
+
+    #[test]
+    fn test_block_trade_cancel_models() {
+        use crate::types::{
+            BlockOfferDetail, BlockOfferStatus, BlockTradeDetail, BlockTradeStatus,
+        };
+
+        let block_trade: BlockTradeDetail = recorded("block-trades/cancel");
+        assert_eq!(block_trade.status, BlockTradeStatus::Cancelled);
+        let offer: BlockOfferDetail = recorded("block-trades/offers/cancel");
+        assert_eq!(offer.block_id, block_trade.block_id);
+        assert_eq!(offer.status, BlockOfferStatus::Cancelled);
+        assert_eq!(offer.orders[0].side, "SELL");
+    }
 }
@@ -1,3 +1,6 @@
+use super::enums::string_enum;
+use crate::{constants::PARACLEAR_DECIMALS, error::QuantumError, utils::to_quantum};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -29,25 +32,18 @@ impl fmt::Display for OrderSide {
     }
 }
 
-/// Order type (Limit/Market/Stop/etc.)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum OrderType {
-    #[serde(rename = "MARKET")]
-    Market,
-    #[serde(rename = "LIMIT")]
-    Limit,
-    #[serde(rename = "STOP_LIMIT")]
-    StopLimit,
-    #[serde(rename = "STOP_MARKET")]
-    StopMarket,
-    #[serde(rename = "TAKE_PROFIT_LIMIT")]
-    TakeProfitLimit,
-    #[serde(rename = "TAKE_PROFIT_MARKET")]
-    TakeProfitMarket,
-    #[serde(rename = "STOP_LOSS_MARKET")]
-    StopLossMarket,
-    #[serde(rename = "STOP_LOSS_LIMIT")]
-    StopLossLimit,
+string_enum! {
+    /// Order type (Limit/Market/Stop/etc.)
+    pub enum OrderType {
+        Market => "MARKET",
+        Limit => "LIMIT",
+        StopLimit => "STOP_LIMIT",
+        StopMarket => "STOP_MARKET",
+        TakeProfitLimit => "TAKE_PROFIT_LIMIT",
+        TakeProfitMarket => "TAKE_PROFIT_MARKET",
+        StopLossMarket => "STOP_LOSS_MARKET",
+        StopLossLimit => "STOP_LOSS_LIMIT",
+    }
 }
 
 impl OrderType {
@@ -63,21 +59,6 @@ impl OrderType {
     }
 }
 
-impl fmt::Display for OrderType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            OrderType::Market => write!(f, "MARKET"),
-            OrderType::Limit => write!(f, "LIMIT"),
-            OrderType::StopLimit => write!(f, "STOP_LIMIT"),
-            OrderType::StopMarket => write!(f, "STOP_MARKET"),
-            OrderType::TakeProfitLimit => write!(f, "TAKE_PROFIT_LIMIT"),
-            OrderType::TakeProfitMarket => write!(f, "TAKE_PROFIT_MARKET"),
-            OrderType::StopLossMarket => write!(f, "STOP_LOSS_MARKET"),
-            OrderType::StopLossLimit => write!(f, "STOP_LOSS_LIMIT"),
-        }
-    }
-}
-
 /// Time in force
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimeInForce {
@@ -182,16 +163,36 @@ impl Order {
     }
 
     /// Convert size to chain-compatible format (quantum with 8 decimals)
-    pub fn chain_size(&self) -> String {
-        self.size.clone()
+    pub fn chain_size(&self) -> Result<String, QuantumError> {
+        chain_quantum(&self.size)
     }
 
     /// Convert price to chain-compatible format (quantum with 8 decimals)
-    pub fn chain_price(&self) -> String {
-        self.price.clone().unwrap_or_else(|| "0".to_string())
+    ///
+    /// Orders without a price (market orders) are signed with a price of 0.
+    pub fn chain_price(&self) -> Result<String, QuantumError> {
+        match &self.price {
+            Some(price) => chain_quantum(price),
+            None => Ok("0".to_string()),
+        }
     }
 }
 
+/// Scale a non-negative decimal string by `PARACLEAR_DECIMALS`
+fn chain_quantum(value: &str) -> Result<String, QuantumError> {
+    let invalid = |reason: String| QuantumError::InvalidDecimal {
+        value: value.to_string(),
+        reason,
+    };
+
+    let decimal = Decimal::from_str_exact(value).map_err(|e| invalid(e.to_string()))?;
+    if decimal.is_sign_negative() && !decimal.is_zero() {
+        return Err(invalid("must not be negative".to_string()));
+    }
+
+    to_quantum(decimal, PARACLEAR_DECIMALS)
+}
+
 /// Order builder for fluent API
 #[derive(Debug, Default)]
 pub struct OrderBuilder {
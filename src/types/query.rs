@@ -0,0 +1,280 @@
+use crate::types::{OrderSide, OrderStatus, TransferStatus};
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Serializer};
+
+/// Time range and cursor fields shared by every paginated endpoint
+///
+/// Timestamps are sent as milliseconds since the epoch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PageQuery {
+    #[serde(serialize_with = "millis", skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<DateTime<Utc>>,
+    #[serde(serialize_with = "millis", skip_serializing_if = "Option::is_none")]
+    pub end_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Query for a paginated list endpoint
+pub trait Paged: Serialize {
+    fn page(&self) -> &PageQuery;
+    fn page_mut(&mut self) -> &mut PageQuery;
+}
+
+macro_rules! paged_query {
+    ($($ty:ty),*) => {
+        $(impl Paged for $ty {
+            fn page(&self) -> &PageQuery {
+                &self.page
+            }
+
+            fn page_mut(&mut self) -> &mut PageQuery {
+                &mut self.page
+            }
+        }
+
+        impl $ty {
+            /// Only return records created at or after `start_at`
+            pub fn start_at(mut self, start_at: DateTime<Utc>) -> Self {
+                self.page.start_at = Some(start_at);
+                self
+            }
+
+            /// Only return records created at or before `end_at`
+            pub fn end_at(mut self, end_at: DateTime<Utc>) -> Self {
+                self.page.end_at = Some(end_at);
+                self
+            }
+
+            /// Number of records per page
+            pub fn page_size(mut self, page_size: u32) -> Self {
+                self.page.page_size = Some(page_size);
+                self
+            }
+
+            /// Resume from a cursor returned by a previous page
+            pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+                self.page.cursor = Some(cursor.into());
+                self
+            }
+        })*
+    };
+}
+
+macro_rules! string_filter {
+    ($($field:ident: $doc:literal),*) => {
+        $(#[doc = $doc]
+        pub fn $field(mut self, $field: impl Into<String>) -> Self {
+            self.$field = Some($field.into());
+            self
+        })*
+    };
+}
+
+/// Query for time-bounded lists without extra filters
+///
+/// Used for transactions, liquidations and tradebusts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HistoryQuery {
+    #[serde(flatten)]
+    pub page: PageQuery,
+}
+
+impl HistoryQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Query for `GET /fills`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FillsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    #[serde(flatten)]
+    pub page: PageQuery,
+}
+
+impl FillsQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    string_filter!(
+        market: "Only return fills for this market",
+        order_id: "Only return fills of this order"
+    );
+}
+
+/// Query for `GET /orders-history`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OrdersHistoryQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<OrderStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<OrderSide>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(flatten)]
+    pub page: PageQuery,
+}
+
+impl OrdersHistoryQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    string_filter!(
+        market: "Only return orders for this market",
+        client_id: "Only return orders with this client id"
+    );
+
+    /// Only return orders in this status
+    pub fn status(mut self, status: OrderStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only return orders on this side
+    pub fn side(mut self, side: OrderSide) -> Self {
+        self.side = Some(side);
+        self
+    }
+}
+
+/// Query for `GET /transfers`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TransfersQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TransferStatus>,
+    #[serde(flatten)]
+    pub page: PageQuery,
+}
+
+impl TransfersQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return transfers in this status
+    pub fn status(mut self, status: TransferStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+}
+
+/// Query for `GET /funding/payments` and the public `GET /funding/data`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FundingQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market: Option<String>,
+    #[serde(flatten)]
+    pub page: PageQuery,
+}
+
+impl FundingQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    string_filter!(market: "Only return funding for this market");
+}
+
+/// Query for the public `GET /trades`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TradesQuery {
+    pub market: String,
+    #[serde(flatten)]
+    pub page: PageQuery,
+}
+
+impl TradesQuery {
+    pub fn new(market: impl Into<String>) -> Self {
+        Self {
+            market: market.into(),
+            page: PageQuery::default(),
+        }
+    }
+}
+
+paged_query!(
+    HistoryQuery,
+    FillsQuery,
+    OrdersHistoryQuery,
+    TransfersQuery,
+    FundingQuery,
+    TradesQuery
+);
+
+/// Flatten a query struct into `(key, value)` pairs for the query string
+pub(crate) fn query_params<Q: Serialize>(query: &Q) -> Vec<(String, String)> {
+    let serde_json::Value::Object(fields) = serde_json::to_value(query).unwrap_or_default() else {
+        return vec![];
+    };
+
+    fields
+        .into_iter()
+        .filter_map(|(key, value)| match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(value) => Some((key, value)),
+            value => Some((key, value.to_string())),
+        })
+        .collect()
+}
+
+fn millis<S: Serializer>(
+    timestamp: &Option<DateTime<Utc>>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match timestamp {
+        Some(timestamp) => serializer.serialize_i64(timestamp.timestamp_millis()),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_orders_history_params() {
+        let query = OrdersHistoryQuery::new()
+            .market("ETH-USD-PERP")
+            .side(OrderSide::Sell)
+            .status(OrderStatus::Closed)
+            .start_at(Utc.timestamp_millis_opt(1_700_000_000_000).unwrap())
+            .page_size(50)
+            .cursor("abc");
+
+        let mut params = query_params(&query);
+        params.sort();
+        let expected = [
+            ("cursor", "abc"),
+            ("market", "ETH-USD-PERP"),
+            ("page_size", "50"),
+            ("side", "SELL"),
+            ("start_at", "1700000000000"),
+            ("status", "CLOSED"),
+        ];
+        assert_eq!(
+            params,
+            expected.map(|(k, v)| (k.to_string(), v.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_empty_query_has_no_params() {
+        assert!(query_params(&FillsQuery::new()).is_empty());
+        assert_eq!(
+            query_params(&TradesQuery::new("BTC-USD-PERP")),
+            [("market".to_string(), "BTC-USD-PERP".to_string())]
+        );
+    }
+}
@@ -1,12 +1,26 @@
 //! Utility functions for Paradex SDK
 
+use crate::error::QuantumError;
 use rust_decimal::Decimal;
 
 /// Convert decimal to quantum (8 decimal places)
-pub fn to_quantum(value: Decimal, decimals: u32) -> String {
-    let multiplier = Decimal::from(10u64.pow(decimals));
-    let quantum = value * multiplier;
-    quantum.normalize().to_string()
+///
+/// Fails instead of rounding when `value` has more than `decimals` decimal
+/// places or the scaled value overflows.
+pub fn to_quantum(value: Decimal, decimals: u32) -> Result<String, QuantumError> {
+    let overflow = || QuantumError::Overflow { value, decimals };
+
+    let multiplier = 10u64
+        .checked_pow(decimals)
+        .map(Decimal::from)
+        .ok_or_else(overflow)?;
+    let quantum = value.checked_mul(multiplier).ok_or_else(overflow)?;
+
+    if !quantum.fract().is_zero() {
+        return Err(QuantumError::PrecisionLoss { value, decimals });
+    }
+
+    Ok(quantum.trunc().normalize().to_string())
 }
 
 /// Convert quantum to decimal
@@ -51,10 +65,28 @@ mod tests {
     #[test]
     fn test_to_quantum() {
         let value = Decimal::from_str_exact("1.5").unwrap();
-        let quantum = to_quantum(value, 8);
+        let quantum = to_quantum(value, 8).unwrap();
         assert_eq!(quantum, "150000000");
     }
 
+    #[test]
+    fn test_to_quantum_precision_loss() {
+        let value = Decimal::from_str_exact("0.000000001").unwrap();
+        assert_eq!(
+            to_quantum(value, 8),
+            Err(QuantumError::PrecisionLoss { value, decimals: 8 })
+        );
+    }
+
+    #[test]
+    fn test_to_quantum_overflow() {
+        let value = Decimal::MAX;
+        assert_eq!(
+            to_quantum(value, 8),
+            Err(QuantumError::Overflow { value, decimals: 8 })
+        );
+    }
+
     #[test]
     fn test_from_quantum() {
         let quantum = "150000000";
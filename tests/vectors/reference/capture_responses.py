@@ -0,0 +1,83 @@
+"""Capture tests/vectors/responses.json from the Paradex testnet.
+
+Fetches every GET endpoint in the fixture and replaces its payload with the
+live response. Private endpoints are fetched when PARADEX_JWT holds a
+testnet token and skipped otherwise; action responses such as
+`block-trades/cancel` can't be fetched and keep their payloads. The value
+assertions in src/types/models.rs follow the recorded payloads and need
+updating after a capture.
+
+    PARADEX_JWT=... python tests/vectors/reference/capture_responses.py
+"""
+
+import json
+import os
+import time
+import urllib.request
+from pathlib import Path
+from urllib.parse import urlencode
+
+FIXTURE = Path(__file__).parent.parent / "responses.json"
+API_URL = "https://api.testnet.paradex.trade/v1"
+MARKET = "BTC-USD-PERP"
+
+now = int(time.time() * 1000)
+PUBLIC = {
+    "system/state": {},
+    "system/time": {},
+    "trades": {"market": MARKET, "page_size": 2},
+    "funding/data": {"market": MARKET, "page_size": 1},
+    "insurance": {},
+    "markets": {"market": MARKET},
+    "markets/klines": {
+        "symbol": MARKET,
+        "resolution": 1,
+        "start_at": now - 3_600_000,
+        "end_at": now,
+    },
+}
+PRIVATE = {
+    "account/profile": {},
+    "account/info": {},
+    "account/subaccounts": {},
+    "liquidations": {"page_size": 1},
+    "tradebusts": {"page_size": 1},
+}
+
+
+def fetch(endpoint, params, token=None):
+    url = f"{API_URL}/{endpoint}"
+    if params:
+        url += "?" + urlencode(params)
+    request = urllib.request.Request(url)
+    if token:
+        request.add_header("Authorization", f"Bearer {token}")
+    with urllib.request.urlopen(request) as response:
+        return json.load(response)
+
+
+def main():
+    fixture = json.loads(FIXTURE.read_text())
+    token = os.environ.get("PARADEX_JWT")
+    captured = []
+
+    for endpoint, params in PUBLIC.items():
+        fixture[endpoint] = fetch(endpoint, params)
+        captured.append(endpoint)
+    for endpoint, params in PRIVATE.items():
+        if not token:
+            print(f"skipped {endpoint}: PARADEX_JWT not set")
+            continue
+        fixture[endpoint] = fetch(endpoint, params, token)
+        captured.append(endpoint)
+
+    day = time.strftime("%Y-%m-%d", time.gmtime())
+    fixture["_source"] = (
+        f"captured from {API_URL} on {day}: {', '.join(captured)}; "
+        "other payloads hand-written from the Paradex API reference"
+    )
+    FIXTURE.write_text(json.dumps(fixture, indent=2) + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,62 @@
+"""Regenerate tests/vectors/key_derivation.json from paradex-py.
+
+Derives each account's L2 key the way paradex-py's `ParadexAccount` does:
+signs the stark key typed message with the L1 key, grinds the signature
+into a Stark key and computes the proxy account address from the fixture's
+class hashes. Writes the L2 key, public key, address and `source` back into
+the fixture.
+
+    pip install paradex-py
+    python tests/vectors/reference/key_derivation_py.py
+"""
+
+import json
+from importlib.metadata import version
+from pathlib import Path
+
+from eth_account import Account
+from eth_account.messages import encode_typed_data
+from paradex_py.account.utils import derive_stark_key
+from paradex_py.message.stark_key import build_stark_key_message
+from starknet_py.hash.address import compute_address
+from starknet_py.hash.selector import get_selector_from_name
+from starknet_py.net.signer.stark_curve_signer import KeyPair
+
+FIXTURE = Path(__file__).parent.parent / "key_derivation.json"
+
+
+def main():
+    fixture = json.loads(FIXTURE.read_text())
+    account_hash = int(fixture["paraclear_account_hash"], 16)
+    proxy_hash = int(fixture["paraclear_account_proxy_hash"], 16)
+    source = f"paradex-py {version('paradex-py')}"
+
+    for vector in fixture["accounts"]:
+        l1 = Account.from_key(vector["l1_private_key"])
+        message = build_stark_key_message(int(vector["l1_chain_id"]))
+        signature = l1.sign_message(encode_typed_data(full_message=message))
+        private_key = derive_stark_key(signature.signature.hex())
+        public_key = KeyPair.from_private_key(private_key).public_key
+        calldata = [
+            account_hash,
+            get_selector_from_name("initialize"),
+            2,
+            public_key,
+            0,
+        ]
+        address = compute_address(
+            class_hash=proxy_hash, constructor_calldata=calldata, salt=public_key
+        )
+        vector.update(
+            l1_address=l1.address,
+            l2_private_key=hex(private_key),
+            l2_public_key=hex(public_key),
+            l2_address=hex(address),
+            source=source,
+        )
+
+    FIXTURE.write_text(json.dumps(fixture, indent=2) + "\n")
+
+
+if __name__ == "__main__":
+    main()
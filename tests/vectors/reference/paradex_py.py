@@ -0,0 +1,85 @@
+"""Regenerate tests/vectors/signatures.json from paradex-py.
+
+Builds each vector's message with the paradex-py message builders, hashes it
+with starknet-py and signs the hash with starknet-py's RFC 6979 signer, then
+writes `message_hash`, `r`, `s` and `source` back into the fixture. Vectors
+whose kind paradex-py has no builder for keep their values and source.
+
+    pip install paradex-py
+    python tests/vectors/reference/paradex_py.py
+"""
+
+import json
+from decimal import Decimal
+from importlib.metadata import version
+from pathlib import Path
+
+from paradex_py.common.order import Order, OrderSide, OrderType
+from paradex_py.message.auth import (
+    build_auth_message,
+    build_fullnode_message,
+    build_onboarding_message,
+)
+from paradex_py.message.order import build_modify_order_message, build_order_message
+from starknet_py.hash.signature import message_signature
+from starknet_py.utils.typed_data import TypedData
+
+FIXTURE = Path(__file__).parent.parent / "signatures.json"
+
+
+def order(input):
+    return Order(
+        market=input["market"],
+        order_type=OrderType(input["type"].capitalize()),
+        order_side=OrderSide(input["side"].capitalize()),
+        size=Decimal(input["size"]),
+        limit_price=Decimal(input.get("price", "0")),
+        order_id=input.get("id"),
+        signature_timestamp=input["signature_timestamp"],
+    )
+
+
+def build(kind, input, chain_id):
+    if kind == "order":
+        return build_order_message(chain_id, order(input))
+    if kind == "modify_order":
+        return build_modify_order_message(chain_id, order(input))
+    if kind == "auth":
+        return build_auth_message(chain_id, input["timestamp"], input["expiry"])
+    if kind == "onboarding":
+        return build_onboarding_message(chain_id)
+    if kind == "fullnode":
+        return build_fullnode_message(
+            chain_id,
+            input["account"],
+            input["payload"],
+            input["timestamp"],
+            input["version"],
+        )
+    return None
+
+
+def main():
+    fixture = json.loads(FIXTURE.read_text())
+    account = fixture["account"]
+    chain_id = int.from_bytes(account["starknet_chain_id"].encode(), "big")
+    private_key = int(account["l2_private_key"], 16)
+    address = int(account["l2_address"], 16)
+    source = f"paradex-py {version('paradex-py')}"
+
+    for vector in fixture["messages"]:
+        message = build(vector["kind"], vector["input"], chain_id)
+        if message is None:
+            print(f"skipped {vector['name']}: no paradex-py builder")
+            continue
+        message_hash = TypedData.from_dict(message).message_hash(address)
+        r, s = message_signature(message_hash, private_key)
+        vector.update(
+            message_hash=hex(message_hash), r=hex(r), s=hex(s), source=source
+        )
+
+    FIXTURE.write_text(json.dumps(fixture, indent=2) + "\n")
+
+
+if __name__ == "__main__":
+    main()
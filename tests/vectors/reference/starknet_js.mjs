@@ -0,0 +1,27 @@
+// Check the (r, s) of tests/vectors/signatures.json against starknet.js.
+//
+// Signs every vector's message hash with the fixture key and fails on any
+// difference, so the signing side is checked by a second implementation
+// for every message type, block trades included.
+//
+//     npm install starknet
+//     node tests/vectors/reference/starknet_js.mjs
+
+import { readFileSync } from "node:fs";
+import { ec, num } from "starknet";
+
+const fixture = JSON.parse(
+  readFileSync(new URL("../signatures.json", import.meta.url), "utf8"),
+);
+const privateKey = fixture.account.l2_private_key;
+
+let failures = 0;
+for (const vector of fixture.messages) {
+  const signature = ec.starkCurve.sign(vector.message_hash, privateKey);
+  const [r, s] = [num.toHex(signature.r), num.toHex(signature.s)];
+  if (r !== vector.r || s !== vector.s) {
+    console.error(`${vector.name}: expected (${vector.r}, ${vector.s}), got (${r}, ${s})`);
+    failures += 1;
+  }
+}
+process.exit(failures === 0 ? 0 : 1);